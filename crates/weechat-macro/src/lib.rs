@@ -182,6 +182,9 @@ pub fn plugin(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         #[doc(hidden)]
         static mut __PLUGIN: Option<#plugin> = None;
 
+        #[doc(hidden)]
+        static mut __UPGRADE_HOOK: Option<weechat::hooks::SignalHook> = None;
+
         /// This function is called when plugin is loaded by WeeChat.
         ///
         /// # Safety
@@ -204,6 +207,7 @@ pub fn plugin(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 Ok(p) => {
                     unsafe {
                         __PLUGIN = Some(p);
+                        __UPGRADE_HOOK = weechat::upgrade::hook_upgrade(#plugin::get).ok();
                     }
                     return weechat::weechat_sys::WEECHAT_RC_OK;
                 }
@@ -224,6 +228,7 @@ pub fn plugin(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             _plugin: *mut weechat::weechat_sys::t_weechat_plugin
         ) -> weechat::libc::c_int {
             unsafe {
+                __UPGRADE_HOOK = None;
                 __PLUGIN = None;
                 Weechat::free();
             }
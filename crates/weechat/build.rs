@@ -2,16 +2,18 @@
 #[allow(unused)]
 #[repr(u64)]
 enum WeechatApiVersions {
-    V4_1_0 = 20230908,
     V4_2_0 = 20240105,
+    V4_3_0 = 20240426,
+    V4_4_0 = 20240727,
 }
 
 fn main() {
     println!("cargo::rerun-if-changed=build.rs");
     println!("cargo::rerun-if-env-changed=WEECHAT_BUNDLED");
     println!("cargo::rerun-if-env-changed=WEECHAT_PLUGIN_FILE");
-    println!("cargo::rustc-check-cfg=cfg(weechat410)");
     println!("cargo::rustc-check-cfg=cfg(weechat420)");
+    println!("cargo::rustc-check-cfg=cfg(weechat430)");
+    println!("cargo::rustc-check-cfg=cfg(weechat440)");
 
     let (version, _) = std::str::from_utf8(weechat_sys::WEECHAT_PLUGIN_API_VERSION)
         .expect("Failed to parse weechat version string")
@@ -22,16 +24,14 @@ fn main() {
 
     let version: u64 = version.parse().expect("Failed to parse weechat version string as u64");
 
+    // Every cfg of an older version is emitted as well, code gated on a
+    // version is meant to be compiled for all the versions that came after it.
     use crate::WeechatApiVersions::*;
-    match version {
-        v if v >= V4_2_0 as _ => {
-            println!("cargo::rustc-cfg=weechat420");
-        }
-        v if v < V4_2_0 as _ => {
-            println!("cargo::rustc-cfg=weechat410");
-        }
-        _ => {
-            println!("cargo::error=Failed to match weechat API version: {version}");
+    let cfgs = [(V4_2_0, "weechat420"), (V4_3_0, "weechat430"), (V4_4_0, "weechat440")];
+
+    for (min_version, cfg) in cfgs {
+        if version >= min_version as u64 {
+            println!("cargo::rustc-cfg={cfg}");
         }
     }
 }
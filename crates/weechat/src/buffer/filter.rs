@@ -0,0 +1,418 @@
+use std::ffi::c_void;
+
+use weechat_sys::{t_hdata, t_weechat_plugin};
+
+use crate::{buffer::Buffer, Weechat};
+
+/// Builder for a Weechat filter.
+///
+/// A filter hides the lines of buffers that match the buffer mask and either
+/// the tags or the regular expression of the filter. Filters are saved in the
+/// Weechat configuration, so they survive a restart of Weechat unless they
+/// are removed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::buffer::FilterBuilder;
+/// let filter = FilterBuilder::new("hide_joins")
+///     .buffer("irc.*")
+///     .tag("irc_join")
+///     .tag("irc_part")
+///     .tag("irc_quit")
+///     .build()
+///     .unwrap();
+///
+/// filter.disable().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FilterBuilder {
+    name: String,
+    buffers: Vec<String>,
+    tags: Vec<String>,
+    regex: Option<String>,
+    enabled: bool,
+    remove_on_drop: bool,
+}
+
+impl FilterBuilder {
+    /// Create a new filter builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the filter, the name can't contain spaces.
+    pub fn new(name: &str) -> Self {
+        FilterBuilder {
+            name: name.to_owned(),
+            buffers: Vec::new(),
+            tags: Vec::new(),
+            regex: None,
+            enabled: true,
+            remove_on_drop: false,
+        }
+    }
+
+    /// Add a buffer mask to the filter, by default the filter applies to all
+    /// buffers.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The full name of a buffer, `*` matches any number of chars
+    ///   and a mask starting with `!` excludes buffers.
+    pub fn buffer(mut self, mask: &str) -> Self {
+        self.buffers.push(mask.to_owned());
+        self
+    }
+
+    /// Add a tag to the filter, lines that have any of the tags are hidden.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag, tags can be combined using `+` to require all of them
+    ///   to be present on a line.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_owned());
+        self
+    }
+
+    /// Set the regular expression of the filter, lines matching the regular
+    /// expression are hidden.
+    ///
+    /// The regular expression is matched against the message, a `\t` separates
+    /// the prefix and the message if the prefix should be matched as well.
+    pub fn regex(mut self, regex: &str) -> Self {
+        self.regex = Some(regex.to_owned());
+        self
+    }
+
+    /// Should the filter be enabled when it's created, defaults to `true`.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Should the filter be removed when the returned handle is dropped.
+    pub fn remove_on_drop(mut self, remove: bool) -> Self {
+        self.remove_on_drop = remove;
+        self
+    }
+
+    /// Create the filter.
+    ///
+    /// A filter with the same name is replaced. Returns an error if Weechat
+    /// rejected the filter, a filter needs either tags or a regular
+    /// expression. The name, the buffer masks and the tags are passed as
+    /// single arguments to the `/filter` command, an error is returned without
+    /// touching the filters if any of them is empty or contains whitespace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn build(self) -> Result<Filter, ()> {
+        Weechat::check_thread();
+
+        let has_regex = self.regex.as_deref().is_some_and(|regex| !regex.is_empty());
+
+        if !is_valid_argument(&self.name)
+            || !self.buffers.iter().all(|mask| is_valid_argument(mask))
+            || !self.tags.iter().all(|tag| is_valid_argument(tag))
+            || (self.tags.is_empty() && !has_regex)
+        {
+            return Err(());
+        }
+
+        let weechat = unsafe { Weechat::weechat() };
+
+        let join = |parts: &[String]| {
+            if parts.is_empty() {
+                "*".to_owned()
+            } else {
+                parts.join(",")
+            }
+        };
+
+        weechat.run_global_command(&format!(
+            "/mute /filter addreplace {} {} {} {}",
+            self.name,
+            join(&self.buffers),
+            join(&self.tags),
+            self.regex.as_deref().filter(|_| has_regex).unwrap_or("*")
+        ))?;
+
+        let mut filter =
+            Filter { name: self.name.clone(), weechat: weechat.ptr, remove_on_drop: false };
+
+        if filter.ptr().is_null() {
+            return Err(());
+        }
+
+        if !self.enabled {
+            filter.disable()?;
+        }
+
+        filter.remove_on_drop = self.remove_on_drop;
+
+        Ok(filter)
+    }
+}
+
+/// A handle to a Weechat filter.
+///
+/// The filter is looked up by its name every time it's used, so the handle
+/// stays valid even if the filter is removed by the user, in which case
+/// modifications of the filter fail.
+pub struct Filter {
+    name: String,
+    weechat: *mut t_weechat_plugin,
+    remove_on_drop: bool,
+}
+
+impl Drop for Filter {
+    fn drop(&mut self) {
+        if self.remove_on_drop {
+            let _ = self.remove_helper();
+        }
+    }
+}
+
+impl Filter {
+    /// Search for an existing filter.
+    ///
+    /// Returns `None` if no filter with the given name exists. The filter is
+    /// not removed when the returned handle is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the filter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn search(name: &str) -> Option<Filter> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let filter = Filter { name: name.to_owned(), weechat: weechat.ptr, remove_on_drop: false };
+
+        if filter.ptr().is_null() {
+            None
+        } else {
+            Some(filter)
+        }
+    }
+
+    fn hdata(weechat: &Weechat) -> *mut t_hdata {
+        unsafe { weechat.hdata_get("filter") }
+    }
+
+    fn ptr(&self) -> *mut c_void {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let hdata = Filter::hdata(&weechat);
+
+        let mut current = unsafe { weechat.hdata_get_list(hdata, "gui_filters") };
+
+        while !current.is_null() {
+            if unsafe { weechat.hdata_string(hdata, current, "name") } == self.name {
+                break;
+            }
+
+            current = unsafe { weechat.hdata_pointer(hdata, current, "next_filter") };
+        }
+
+        current
+    }
+
+    fn string(&self, name: &str) -> String {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let ptr = self.ptr();
+
+        if ptr.is_null() {
+            String::new()
+        } else {
+            unsafe { weechat.hdata_string(Filter::hdata(&weechat), ptr, name).into_owned() }
+        }
+    }
+
+    /// The name of the filter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Does the filter still exist.
+    pub fn exists(&self) -> bool {
+        !self.ptr().is_null()
+    }
+
+    /// Is the filter enabled.
+    ///
+    /// Returns `false` if the filter doesn't exist anymore.
+    pub fn enabled(&self) -> bool {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let ptr = self.ptr();
+
+        !ptr.is_null()
+            && unsafe { weechat.hdata_integer(Filter::hdata(&weechat), ptr, "enabled") != 0 }
+    }
+
+    /// The buffer masks of the filter.
+    pub fn buffers(&self) -> Vec<String> {
+        split_list(&self.string("buffer_name"))
+    }
+
+    /// The tags of the filter, `*` if the filter doesn't filter on tags.
+    pub fn tags(&self) -> Vec<String> {
+        split_list(&self.string("tags"))
+    }
+
+    /// The regular expression of the filter, `*` if the filter doesn't filter
+    /// on the content of lines.
+    pub fn regex(&self) -> String {
+        self.string("regex")
+    }
+
+    fn set_enabled(&self, enabled: bool) -> Result<(), ()> {
+        if !is_valid_argument(&self.name) || !self.exists() {
+            return Err(());
+        }
+
+        let weechat = Weechat::from_ptr(self.weechat);
+        let action = if enabled { "enable" } else { "disable" };
+
+        weechat.run_global_command(&format!("/mute /filter {action} {}", self.name))?;
+
+        if self.enabled() == enabled {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Enable the filter.
+    ///
+    /// Returns an error if the filter doesn't exist anymore.
+    pub fn enable(&self) -> Result<(), ()> {
+        self.set_enabled(true)
+    }
+
+    /// Disable the filter.
+    ///
+    /// Returns an error if the filter doesn't exist anymore.
+    pub fn disable(&self) -> Result<(), ()> {
+        self.set_enabled(false)
+    }
+
+    /// Set if the filter should be removed when this handle is dropped.
+    pub fn set_remove_on_drop(&mut self, remove: bool) {
+        self.remove_on_drop = remove;
+    }
+
+    fn remove_helper(&self) -> Result<(), ()> {
+        if !is_valid_argument(&self.name) || !self.exists() {
+            return Err(());
+        }
+
+        let weechat = Weechat::from_ptr(self.weechat);
+        weechat.run_global_command(&format!("/mute /filter del {}", self.name))?;
+
+        if self.exists() {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Remove the filter.
+    ///
+    /// Returns an error if the filter doesn't exist anymore.
+    pub fn remove(mut self) -> Result<(), ()> {
+        self.remove_on_drop = false;
+        self.remove_helper()
+    }
+}
+
+/// Can the string be passed as a single argument to the `/filter` command.
+fn is_valid_argument(argument: &str) -> bool {
+    !argument.is_empty() && !argument.contains(char::is_whitespace)
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    if list.is_empty() {
+        Vec::new()
+    } else {
+        list.split(',').map(|s| s.to_owned()).collect()
+    }
+}
+
+/// An iterator over the filters of Weechat.
+pub struct Filters<'a> {
+    weechat: &'a Weechat,
+    hdata: *mut t_hdata,
+    current: *mut c_void,
+}
+
+impl<'a> Filters<'a> {
+    pub(crate) fn new(weechat: &'a Weechat) -> Self {
+        let hdata = Filter::hdata(weechat);
+        let current = unsafe { weechat.hdata_get_list(hdata, "gui_filters") };
+
+        Filters { weechat, hdata, current }
+    }
+}
+
+impl Iterator for Filters<'_> {
+    type Item = Filter;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            None
+        } else {
+            let name = unsafe { self.weechat.hdata_string(self.hdata, self.current, "name") };
+            let filter = Filter {
+                name: name.into_owned(),
+                weechat: self.weechat.ptr,
+                remove_on_drop: false,
+            };
+
+            self.current =
+                unsafe { self.weechat.hdata_pointer(self.hdata, self.current, "next_filter") };
+
+            Some(filter)
+        }
+    }
+}
+
+impl Weechat {
+    /// Get an iterator over all the filters.
+    pub fn filters(&self) -> Filters<'_> {
+        Filters::new(self)
+    }
+
+    /// Are filters enabled globally.
+    ///
+    /// If filters are disabled globally no lines are hidden, regardless of
+    /// the state of individual filters and buffers.
+    pub fn filters_enabled(&self) -> bool {
+        Weechat::info_get("filters_enabled", "").as_deref() == Some("1")
+    }
+
+    /// Enable or disable filters globally.
+    pub fn set_filters_enabled(&self, enabled: bool) -> Result<(), ()> {
+        let action = if enabled { "enable" } else { "disable" };
+        self.run_global_command(&format!("/mute /filter {action}"))
+    }
+}
+
+impl Buffer<'_> {
+    /// Are filters enabled for this buffer.
+    pub fn filters_enabled(&self) -> bool {
+        self.get_integer("filter") != 0
+    }
+
+    /// Enable or disable filters for this buffer.
+    ///
+    /// Weechat sends the `buffer_filters_enabled` or `buffer_filters_disabled`
+    /// signal with the buffer when the state changes.
+    pub fn set_filters_enabled(&self, enabled: bool) {
+        self.set("filter", if enabled { "1" } else { "0" });
+    }
+}
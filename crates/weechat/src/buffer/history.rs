@@ -0,0 +1,97 @@
+use std::{collections::HashMap, ffi::c_void, ptr};
+
+use weechat_sys::t_hdata;
+
+use crate::{buffer::Buffer, Weechat};
+
+/// An iterator over the entries of an input history, the newest entry comes
+/// first.
+pub struct History<'a> {
+    weechat: &'a Weechat,
+    hdata: *mut t_hdata,
+    current: *mut c_void,
+}
+
+impl<'a> History<'a> {
+    fn new(weechat: &'a Weechat, first: impl FnOnce(*mut t_hdata) -> *mut c_void) -> Self {
+        let hdata = unsafe { weechat.hdata_get("history") };
+        let current = first(hdata);
+
+        History { weechat, hdata, current }
+    }
+}
+
+impl Iterator for History<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            None
+        } else {
+            let text =
+                unsafe { self.weechat.hdata_string(self.hdata, self.current, "text").into_owned() };
+
+            self.current =
+                unsafe { self.weechat.hdata_pointer(self.hdata, self.current, "next_history") };
+
+            Some(text)
+        }
+    }
+}
+
+impl Weechat {
+    /// Get an iterator over the global input history.
+    ///
+    /// The global history contains the input of all buffers.
+    pub fn global_history(&self) -> History<'_> {
+        History::new(self, |hdata| unsafe { self.hdata_get_list(hdata, "gui_history") })
+    }
+}
+
+impl Buffer<'_> {
+    /// Get an iterator over the input history of the buffer.
+    pub fn history(&self) -> History<'_> {
+        let weechat = self.weechat();
+
+        History::new(weechat, |_| unsafe {
+            weechat.hdata_pointer(self.hdata_pointer(), self.ptr() as _, "history")
+        })
+    }
+
+    /// Add text to the input history of the buffer.
+    ///
+    /// The text is added to the global history as well. The text passes
+    /// through the `history_add` modifier first, so other plugins may change
+    /// it or prevent it from being added.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text that should be added to the history.
+    pub fn add_to_history(&self, text: &str) {
+        let weechat = self.weechat();
+        let buffer = format!("{:p}", self.ptr());
+
+        // Weechat adds the original text if the modifier failed and nothing if
+        // the modifier returned an empty string.
+        let text = Weechat::execute_modifier("history_add", &buffer, text)
+            .unwrap_or_else(|_| text.to_owned());
+
+        if text.is_empty() {
+            return;
+        }
+
+        let mut buffer_entry = HashMap::new();
+        buffer_entry.insert("buffer", buffer.as_str());
+        buffer_entry.insert("text", text.as_str());
+
+        // Without a buffer the text is added to the global history.
+        let mut global_entry = HashMap::new();
+        global_entry.insert("text", text.as_str());
+
+        unsafe {
+            let hdata = weechat.hdata_get("history");
+            weechat.hdata_update(hdata, ptr::null_mut(), buffer_entry);
+            weechat.hdata_update(hdata, ptr::null_mut(), global_entry);
+        }
+    }
+}
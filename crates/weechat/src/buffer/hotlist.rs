@@ -0,0 +1,164 @@
+use std::{
+    ffi::c_void,
+    time::{Duration, SystemTime},
+};
+
+use weechat_sys::t_hdata;
+
+use crate::{buffer::Buffer, Weechat};
+
+/// The priority of a hotlist entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HotlistPriority {
+    /// A low priority message, for example a join or a part message.
+    Low,
+    /// A normal message.
+    Message,
+    /// A private message.
+    Private,
+    /// A highlight.
+    Highlight,
+}
+
+impl HotlistPriority {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            HotlistPriority::Low => "0",
+            HotlistPriority::Message => "1",
+            HotlistPriority::Private => "2",
+            HotlistPriority::Highlight => "3",
+        }
+    }
+
+    pub(crate) fn from_int(priority: i32) -> HotlistPriority {
+        match priority {
+            1 => HotlistPriority::Message,
+            2 => HotlistPriority::Private,
+            3 => HotlistPriority::Highlight,
+            _ => HotlistPriority::Low,
+        }
+    }
+}
+
+/// The number of messages of every priority that a hotlist entry contains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HotlistCounts {
+    /// The number of low priority messages.
+    pub low: i32,
+    /// The number of normal messages.
+    pub message: i32,
+    /// The number of private messages.
+    pub private: i32,
+    /// The number of highlights.
+    pub highlight: i32,
+}
+
+impl HotlistCounts {
+    /// Get the number of messages with the given priority.
+    pub fn get(&self, priority: HotlistPriority) -> i32 {
+        match priority {
+            HotlistPriority::Low => self.low,
+            HotlistPriority::Message => self.message,
+            HotlistPriority::Private => self.private,
+            HotlistPriority::Highlight => self.highlight,
+        }
+    }
+
+    /// The number of messages of all priorities.
+    pub fn total(&self) -> i32 {
+        self.low + self.message + self.private + self.highlight
+    }
+}
+
+/// An entry of the Weechat hotlist.
+pub struct HotlistEntry<'a> {
+    buffer: Buffer<'a>,
+    priority: HotlistPriority,
+    creation_time: SystemTime,
+    counts: HotlistCounts,
+}
+
+impl<'a> HotlistEntry<'a> {
+    /// The buffer the entry belongs to.
+    pub fn buffer(&self) -> &Buffer<'a> {
+        &self.buffer
+    }
+
+    /// The highest priority of the messages in the entry.
+    pub fn priority(&self) -> HotlistPriority {
+        self.priority
+    }
+
+    /// The time when the entry was added to the hotlist.
+    pub fn creation_time(&self) -> SystemTime {
+        self.creation_time
+    }
+
+    /// The number of messages of every priority.
+    pub fn counts(&self) -> HotlistCounts {
+        self.counts
+    }
+}
+
+/// An iterator over the entries of the Weechat hotlist.
+pub struct Hotlist<'a> {
+    weechat: &'a Weechat,
+    hdata: *mut t_hdata,
+    current: *mut c_void,
+}
+
+impl<'a> Hotlist<'a> {
+    pub(crate) fn new(weechat: &'a Weechat) -> Self {
+        let hdata = unsafe { weechat.hdata_get("hotlist") };
+        let current = unsafe { weechat.hdata_get_list(hdata, "gui_hotlist") };
+
+        Hotlist { weechat, hdata, current }
+    }
+
+    fn entry(&self) -> HotlistEntry<'a> {
+        let weechat = self.weechat;
+        let (hdata, pointer) = (self.hdata, self.current);
+
+        let buffer = unsafe { weechat.hdata_pointer(hdata, pointer, "buffer") };
+        let priority = unsafe { weechat.hdata_integer(hdata, pointer, "priority") };
+
+        let seconds = unsafe { weechat.hdata_time(hdata, pointer, "creation_time.tv_sec") };
+        let micros = unsafe { weechat.hdata_long(hdata, pointer, "creation_time.tv_usec") };
+        let creation_time = SystemTime::UNIX_EPOCH
+            + Duration::from_secs(seconds as u64)
+            + Duration::from_micros(micros as u64);
+
+        let count = |priority: HotlistPriority| unsafe {
+            weechat.hdata_integer(hdata, pointer, &format!("{}|count", priority.as_str()))
+        };
+
+        HotlistEntry {
+            buffer: weechat.buffer_from_ptr(buffer as _),
+            priority: HotlistPriority::from_int(priority),
+            creation_time,
+            counts: HotlistCounts {
+                low: count(HotlistPriority::Low),
+                message: count(HotlistPriority::Message),
+                private: count(HotlistPriority::Private),
+                highlight: count(HotlistPriority::Highlight),
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for Hotlist<'a> {
+    type Item = HotlistEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            None
+        } else {
+            let entry = self.entry();
+
+            self.current =
+                unsafe { self.weechat.hdata_pointer(self.hdata, self.current, "next_hotlist") };
+
+            Some(entry)
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::{ffi::c_void, marker::PhantomData};
+
+use weechat_sys::t_hdata;
+
+use crate::{
+    buffer::{Buffer, BufferKind, Window},
+    regex::Regex,
+    Weechat,
+};
+
+/// An iterator over the buffers of Weechat.
+///
+/// The iterator can be narrowed down using the filter methods, only buffers
+/// matching all of the filters are returned.
+pub struct Buffers<'a> {
+    weechat: &'a Weechat,
+    hdata: *mut t_hdata,
+    current: *mut c_void,
+    plugin: Option<String>,
+    localvars: Vec<(String, String)>,
+    kind: Option<BufferKind>,
+    full_name: Option<Regex>,
+}
+
+impl<'a> Buffers<'a> {
+    pub(crate) fn new(weechat: &'a Weechat) -> Self {
+        let hdata = unsafe { weechat.hdata_get("buffer") };
+        let current = unsafe { weechat.hdata_get_list(hdata, "gui_buffers") };
+
+        Buffers {
+            weechat,
+            hdata,
+            current,
+            plugin: None,
+            localvars: Vec::new(),
+            kind: None,
+            full_name: None,
+        }
+    }
+
+    /// Only return buffers that belong to the given plugin.
+    ///
+    /// # Arguments
+    ///
+    /// * `plugin_name` - The name of the plugin, buffers that Weechat itself
+    ///   created belong to the "core" plugin.
+    pub fn plugin(mut self, plugin_name: &str) -> Self {
+        self.plugin = Some(plugin_name.to_owned());
+        self
+    }
+
+    /// Only return buffers that have the given local variable set to the given
+    /// value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the local variable.
+    ///
+    /// * `value` - The value the local variable needs to have.
+    pub fn localvar(mut self, name: &str, value: &str) -> Self {
+        self.localvars.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Only return buffers of the given kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind the buffer needs to have.
+    pub fn kind(mut self, kind: BufferKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub(crate) fn full_name_regex(mut self, regex: Regex) -> Self {
+        self.full_name = Some(regex);
+        self
+    }
+
+    fn matches(&self, buffer: &Buffer) -> bool {
+        if let Some(plugin) = &self.plugin {
+            if buffer.plugin_name() != plugin.as_str() {
+                return false;
+            }
+        }
+
+        if let Some(kind) = self.kind {
+            if buffer.kind() != kind {
+                return false;
+            }
+        }
+
+        if let Some(regex) = &self.full_name {
+            if !regex.is_match(&buffer.full_name()) {
+                return false;
+            }
+        }
+
+        self.localvars
+            .iter()
+            .all(|(name, value)| buffer.get_localvar(name).as_deref() == Some(value.as_str()))
+    }
+}
+
+impl<'a> Iterator for Buffers<'a> {
+    type Item = Buffer<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.current.is_null() {
+            let buffer = self.weechat.buffer_from_ptr(self.current as _);
+
+            self.current =
+                unsafe { self.weechat.hdata_pointer(self.hdata, self.current, "next_buffer") };
+
+            if self.matches(&buffer) {
+                return Some(buffer);
+            }
+        }
+
+        None
+    }
+}
+
+/// An iterator over the windows of Weechat.
+pub struct Windows<'a> {
+    weechat: &'a Weechat,
+    hdata: *mut t_hdata,
+    current: *mut c_void,
+}
+
+impl<'a> Windows<'a> {
+    pub(crate) fn new(weechat: &'a Weechat) -> Self {
+        let hdata = unsafe { weechat.hdata_get("window") };
+        let current = unsafe { weechat.hdata_get_list(hdata, "gui_windows") };
+
+        Windows { weechat, hdata, current }
+    }
+}
+
+impl<'a> Iterator for Windows<'a> {
+    type Item = Window<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            None
+        } else {
+            let window =
+                Window { weechat: self.weechat.ptr, ptr: self.current as _, phantom: PhantomData };
+
+            self.current =
+                unsafe { self.weechat.hdata_pointer(self.hdata, self.current, "next_window") };
+
+            Some(window)
+        }
+    }
+}
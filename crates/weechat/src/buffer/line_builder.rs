@@ -0,0 +1,278 @@
+use std::{ptr, time::SystemTime};
+
+use crate::{buffer::Buffer, LossyCString};
+
+/// The notify level of a printed line.
+///
+/// The notify level decides how the line affects the hotlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notify {
+    /// The line isn't added to the hotlist.
+    None,
+    /// The line is added to the hotlist as a normal message.
+    Message,
+    /// The line is added to the hotlist as a private message.
+    Private,
+    /// The line is added to the hotlist as a highlight.
+    Highlight,
+}
+
+impl Notify {
+    pub(crate) fn from_tag(tag: &str) -> Option<Notify> {
+        match tag {
+            "notify_none" => Some(Notify::None),
+            "notify_message" => Some(Notify::Message),
+            "notify_private" => Some(Notify::Private),
+            "notify_highlight" => Some(Notify::Highlight),
+            _ => None,
+        }
+    }
+
+    fn as_tag(&self) -> &'static str {
+        match self {
+            Notify::None => "notify_none",
+            Notify::Message => "notify_message",
+            Notify::Private => "notify_private",
+            Notify::Highlight => "notify_highlight",
+        }
+    }
+}
+
+/// Builder for a line that will be printed on a buffer.
+///
+/// The builder takes care of the conventions Weechat and its plugins use for
+/// the prefix and the tags of a line.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::SystemTime;
+/// # use weechat::buffer::{BufferBuilder, LineBuilder, Notify};
+/// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+/// # let buffer = buffer_handle.upgrade().unwrap();
+/// LineBuilder::new("Hello world")
+///     .prefix("alice")
+///     .nick("alice")
+///     .notify(Notify::Message)
+///     .tag("matrix_message")
+///     .date(SystemTime::now())
+///     .print(&buffer);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineBuilder {
+    prefix: Option<String>,
+    message: String,
+    notify: Option<Notify>,
+    nick: Option<String>,
+    self_message: bool,
+    log_level: Option<u8>,
+    tags: Vec<String>,
+    date: Option<SystemTime>,
+}
+
+impl LineBuilder {
+    /// Create a builder for a new line.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message of the line.
+    pub fn new(message: &str) -> Self {
+        LineBuilder {
+            prefix: None,
+            message: message.to_owned(),
+            notify: None,
+            nick: None,
+            self_message: false,
+            log_level: None,
+            tags: Vec::new(),
+            date: None,
+        }
+    }
+
+    /// Set the prefix of the line, the prefix is displayed before the message,
+    /// usually it's the nick of the sender of the message.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_owned());
+        self
+    }
+
+    /// Set the notify level of the line.
+    ///
+    /// If no notify level is set the notify level of the buffer is used.
+    pub fn notify(mut self, notify: Notify) -> Self {
+        self.notify = Some(notify);
+        self
+    }
+
+    /// Set the nick of the sender of the message, this adds the `nick_` tag
+    /// to the line.
+    pub fn nick(mut self, nick: &str) -> Self {
+        self.nick = Some(nick.to_owned());
+        self
+    }
+
+    /// Mark the line as a message that was sent by the user.
+    ///
+    /// Messages of the user don't highlight and, unless a notify level is
+    /// set, aren't added to the hotlist.
+    pub fn self_message(mut self, self_message: bool) -> Self {
+        self.self_message = self_message;
+        self
+    }
+
+    /// Set the level at which the line is logged by the logger plugin.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The log level, between 1 and 9. A log level of 0 disables
+    ///   logging of the line.
+    pub fn log_level(mut self, level: u8) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Add a tag to the line.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_owned());
+        self
+    }
+
+    /// Add multiple tags to the line.
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.tags.extend(tags.iter().map(|t| (*t).to_owned()));
+        self
+    }
+
+    /// Set the date of the line, by default the current time is used.
+    ///
+    /// Microseconds are only used on Weechat versions that support them.
+    pub fn date(mut self, date: SystemTime) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    fn build_tags(&self) -> String {
+        let mut tags: Vec<String> = Vec::new();
+
+        if self.self_message {
+            tags.push("self_msg".to_owned());
+            tags.push("no_highlight".to_owned());
+        }
+
+        match self.notify {
+            Some(notify) => tags.push(notify.as_tag().to_owned()),
+            None if self.self_message => tags.push(Notify::None.as_tag().to_owned()),
+            None => (),
+        }
+
+        if let Some(nick) = &self.nick {
+            tags.push(format!("nick_{nick}"));
+        }
+
+        match self.log_level {
+            Some(0) => tags.push("no_log".to_owned()),
+            Some(level) => tags.push(format!("log{}", level.min(9))),
+            None => (),
+        }
+
+        tags.extend(self.tags.iter().cloned());
+
+        tags.join(",")
+    }
+
+    fn build_message(&self) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}\t{}", self.message),
+            None => self.message.clone(),
+        }
+    }
+
+    /// Print the line on the given buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer the line should be printed on.
+    pub fn print(&self, buffer: &Buffer) {
+        let weechat = buffer.weechat();
+
+        #[cfg(weechat420)]
+        let printf_date_tags = weechat.get().printf_datetime_tags.unwrap();
+        #[cfg(not(weechat420))]
+        let printf_date_tags = weechat.get().printf_date_tags.unwrap();
+
+        let since_epoch =
+            self.date.and_then(|date| date.duration_since(SystemTime::UNIX_EPOCH).ok());
+
+        let date = since_epoch.map(|d| d.as_secs()).unwrap_or_default();
+        #[cfg(weechat420)]
+        let date_usec = since_epoch.map(|d| d.subsec_micros()).unwrap_or_default();
+
+        let fmt_str = LossyCString::new("%s");
+        let tags = self.build_tags();
+        let c_tags = LossyCString::new(&tags);
+        let tags_ptr = if tags.is_empty() { ptr::null() } else { c_tags.as_ptr() };
+        let message = LossyCString::new(self.build_message());
+
+        unsafe {
+            printf_date_tags(
+                buffer.ptr(),
+                date as _,
+                #[cfg(weechat420)]
+                date_usec as _,
+                tags_ptr,
+                fmt_str.as_ptr(),
+                message.as_ptr(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_line_has_no_tags() {
+        assert_eq!(LineBuilder::new("Hello").build_tags(), "");
+    }
+
+    #[test]
+    fn self_message_implies_notify_none() {
+        let tags = LineBuilder::new("Hello").self_message(true).build_tags();
+        assert_eq!(tags, "self_msg,no_highlight,notify_none");
+    }
+
+    #[test]
+    fn notify_overrides_self_message_default() {
+        let tags =
+            LineBuilder::new("Hello").self_message(true).notify(Notify::Private).build_tags();
+        assert_eq!(tags, "self_msg,no_highlight,notify_private");
+    }
+
+    #[test]
+    fn log_level_zero_disables_logging() {
+        assert_eq!(LineBuilder::new("Hello").log_level(0).build_tags(), "no_log");
+    }
+
+    #[test]
+    fn log_level_is_clamped() {
+        assert_eq!(LineBuilder::new("Hello").log_level(3).build_tags(), "log3");
+        assert_eq!(LineBuilder::new("Hello").log_level(42).build_tags(), "log9");
+    }
+
+    #[test]
+    fn custom_tags_come_last() {
+        let tags = LineBuilder::new("Hello")
+            .nick("alice")
+            .notify(Notify::Message)
+            .tags(&["matrix_message", "matrix_id_1"])
+            .build_tags();
+        assert_eq!(tags, "notify_message,nick_alice,matrix_message,matrix_id_1");
+    }
+
+    #[test]
+    fn prefix_is_separated_by_a_tab() {
+        assert_eq!(LineBuilder::new("Hello").prefix("alice").build_message(), "alice\tHello");
+        assert_eq!(LineBuilder::new("Hello").build_message(), "Hello");
+    }
+}
@@ -2,7 +2,10 @@ use std::{borrow::Cow, collections::HashMap, ffi::c_void, marker::PhantomData};
 
 use weechat_sys::{t_hdata, t_weechat_plugin};
 
-use crate::{buffer::Buffer, Weechat};
+use crate::{
+    buffer::{Buffer, HotlistPriority, Notify},
+    Weechat,
+};
 
 /// An iterator that steps over the lines of the buffer.
 pub struct BufferLines<'a> {
@@ -35,9 +38,10 @@ impl<'a> Iterator for BufferLines<'a> {
                 self.done = true;
             }
 
+            let line_pointer = self.first_line;
             self.first_line = unsafe { weechat.hdata_move(line_hdata, self.first_line, 1) };
 
-            Some(BufferLine { weechat, line_data_pointer, buffer: PhantomData })
+            Some(BufferLine { weechat, line_pointer, line_data_pointer, buffer: PhantomData })
         }
     }
 }
@@ -62,9 +66,10 @@ impl DoubleEndedIterator for BufferLines<'_> {
                 self.done = true;
             }
 
+            let line_pointer = self.last_line;
             self.last_line = unsafe { weechat.hdata_move(line_hdata, self.last_line, -1) };
 
-            Some(BufferLine { weechat, line_data_pointer, buffer: PhantomData })
+            Some(BufferLine { weechat, line_pointer, line_data_pointer, buffer: PhantomData })
         }
     }
 }
@@ -80,10 +85,147 @@ pub struct LineData<'a> {
     pub tags: Option<&'a [&'a str]>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum LineKey {
+    #[cfg(weechat440)]
+    Id(i32),
+    Tag(String),
+}
+
+/// An owned identifier of a buffer line.
+///
+/// Unlike a [`BufferLine`] the identifier can be kept around and used to find
+/// the line again later on, for example to edit or redact a message, using
+/// [`Buffer::line_by_id`].
+///
+/// On Weechat 4.4 and newer lines are identified by the id Weechat assigns to
+/// every line, on older versions a tag that is unique to the line needs to be
+/// used.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LineId {
+    key: LineKey,
+}
+
+impl LineId {
+    /// Create a line identifier that finds the line using a tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - A tag that only the line that should be found has, for
+    ///   example a tag containing the event id of a message.
+    pub fn from_tag(tag: &str) -> Self {
+        LineId { key: LineKey::Tag(tag.to_owned()) }
+    }
+
+    /// Create a line identifier from the id Weechat assigned to the line.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the line.
+    #[cfg(weechat440)]
+    pub fn from_id(id: i32) -> Self {
+        LineId { key: LineKey::Id(id) }
+    }
+
+    /// The id Weechat assigned to the line, `None` if the line is identified
+    /// by a tag.
+    #[cfg(weechat440)]
+    pub fn id(&self) -> Option<i32> {
+        match self.key {
+            LineKey::Id(id) => Some(id),
+            LineKey::Tag(_) => None,
+        }
+    }
+
+    /// The tag that identifies the line, `None` if the line is identified by
+    /// its id.
+    pub fn tag(&self) -> Option<&str> {
+        match &self.key {
+            #[cfg(weechat440)]
+            LineKey::Id(_) => None,
+            LineKey::Tag(tag) => Some(tag),
+        }
+    }
+}
+
+impl Buffer<'_> {
+    pub(crate) fn first_line_pointer(&self) -> *mut c_void {
+        let weechat = self.weechat();
+
+        unsafe {
+            let lines = weechat.hdata_get("lines");
+            weechat.hdata_pointer(lines, self.own_lines(), "first_line")
+        }
+    }
+
+    pub(crate) fn last_line_pointer(&self) -> *mut c_void {
+        let weechat = self.weechat();
+
+        unsafe {
+            let lines = weechat.hdata_get("lines");
+            weechat.hdata_pointer(lines, self.own_lines(), "last_line")
+        }
+    }
+
+    fn line_from_pointer(&self, line_pointer: *mut c_void) -> Option<BufferLine<'_>> {
+        let weechat = Weechat::from_ptr(self.weechat().ptr);
+
+        let line_data_pointer = unsafe {
+            let line_hdata = weechat.hdata_get("line");
+            weechat.hdata_pointer(line_hdata, line_pointer, "data")
+        };
+
+        if line_data_pointer.is_null() {
+            None
+        } else {
+            Some(BufferLine { weechat, line_pointer, line_data_pointer, buffer: PhantomData })
+        }
+    }
+
+    /// Find a line using its identifier.
+    ///
+    /// Returns `None` if the line doesn't exist anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the line.
+    pub fn line_by_id(&self, id: &LineId) -> Option<BufferLine<'_>> {
+        match &id.key {
+            #[cfg(weechat440)]
+            LineKey::Id(line_id) => {
+                let weechat = self.weechat();
+                let line_search_by_id = weechat.get().line_search_by_id.unwrap();
+                let line_pointer = unsafe { line_search_by_id(self.ptr(), *line_id) };
+
+                if line_pointer.is_null() {
+                    None
+                } else {
+                    self.line_from_pointer(line_pointer as _)
+                }
+            }
+            // Lines that are looked up again are usually recent ones, the
+            // search starts at the newest line.
+            LineKey::Tag(tag) => self.find_line_by_tag(tag),
+        }
+    }
+
+    /// Find the most recent line that has the given tag.
+    ///
+    /// Returns `None` if no line has the tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag the line needs to have.
+    pub fn find_line_by_tag(&self, tag: &str) -> Option<BufferLine<'_>> {
+        self.lines().rev().find(|line| line.has_tag(tag))
+    }
+}
+
 /// The buffer line, makes it possible to modify the printed message and other
 /// line data.
 pub struct BufferLine<'a> {
     weechat: Weechat,
+    line_pointer: *mut c_void,
     line_data_pointer: *mut c_void,
     buffer: PhantomData<&'a Buffer<'a>>,
 }
@@ -93,12 +235,49 @@ impl<'a> BufferLine<'a> {
         unsafe { self.weechat.hdata_get("line_data") }
     }
 
+    pub(crate) fn line_pointer(&self) -> *mut c_void {
+        self.line_pointer
+    }
+
     fn update_line(&self, hashmap: HashMap<&str, &str>) {
         unsafe {
             self.weechat.hdata_update(self.hdata(), self.line_data_pointer, hashmap);
         }
     }
 
+    /// Get an owned identifier for the line.
+    ///
+    /// The identifier uses the id Weechat assigned to the line.
+    #[cfg(weechat440)]
+    pub fn id(&self) -> LineId {
+        LineId { key: LineKey::Id(self.id_number()) }
+    }
+
+    /// Get an owned identifier for the line that finds the line using the
+    /// given tag.
+    ///
+    /// Returns `None` if the line doesn't have the tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - A tag of the line that no other line of the buffer has.
+    pub fn id_from_tag(&self, tag: &str) -> Option<LineId> {
+        if self.has_tag(tag) {
+            Some(LineId { key: LineKey::Tag(tag.to_owned()) })
+        } else {
+            None
+        }
+    }
+
+    #[cfg(weechat440)]
+    fn id_number(&self) -> i32 {
+        unsafe { self.weechat.hdata_integer(self.hdata(), self.line_data_pointer, "id") }
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.tags().iter().any(|t| t == tag)
+    }
+
     /// Get the prefix of the line, everything left of the message separator
     /// (usually `|`) is considered the prefix.
     pub fn prefix(&self) -> Cow<'_, str> {
@@ -160,7 +339,7 @@ impl<'a> BufferLine<'a> {
     /// # Arguments
     ///
     /// * `new_value` - The new date that should be set on the line.
-    pub fn set_date_printed(&self, new_value: &str) {
+    pub fn set_date_printed(&self, new_value: i64) {
         let mut hashmap = HashMap::new();
         let date = new_value.to_string();
         hashmap.insert("date_printed", date.as_ref());
@@ -172,6 +351,54 @@ impl<'a> BufferLine<'a> {
         unsafe { self.weechat.hdata_char(self.hdata(), self.line_data_pointer, "highlight") != 0 }
     }
 
+    /// Is the line displayed, lines that are hidden by a filter aren't
+    /// displayed.
+    pub fn displayed(&self) -> bool {
+        unsafe { self.weechat.hdata_char(self.hdata(), self.line_data_pointer, "displayed") != 0 }
+    }
+
+    /// Does the line need to be redrawn.
+    pub fn refresh_needed(&self) -> bool {
+        unsafe {
+            self.weechat.hdata_char(self.hdata(), self.line_data_pointer, "refresh_needed") != 0
+        }
+    }
+
+    /// Get the priority with which the line was added to the hotlist.
+    ///
+    /// Returns `None` if the line wasn't added to the hotlist.
+    pub fn notify_level(&self) -> Option<HotlistPriority> {
+        let level = unsafe {
+            self.weechat.hdata_char(self.hdata(), self.line_data_pointer, "notify_level") as i8
+        };
+
+        if level < 0 {
+            None
+        } else {
+            Some(HotlistPriority::from_int(level as i32))
+        }
+    }
+
+    /// Get the line number of the line, only lines of buffers with free
+    /// content have a line number.
+    pub fn y(&self) -> i32 {
+        unsafe { self.weechat.hdata_integer(self.hdata(), self.line_data_pointer, "y") }
+    }
+
+    /// Get the length of the prefix of the line, in chars displayed on the
+    /// screen.
+    pub fn prefix_length(&self) -> i32 {
+        unsafe { self.weechat.hdata_integer(self.hdata(), self.line_data_pointer, "prefix_length") }
+    }
+
+    /// Get the buffer the line belongs to.
+    pub fn buffer(&self) -> Buffer<'_> {
+        let buffer =
+            unsafe { self.weechat.hdata_pointer(self.hdata(), self.line_data_pointer, "buffer") };
+
+        self.weechat.buffer_from_ptr(buffer as _)
+    }
+
     /// Get the list of tags of the line.
     pub fn tags(&self) -> Vec<Cow<'_, str>> {
         unsafe {
@@ -196,6 +423,11 @@ impl<'a> BufferLine<'a> {
         }
     }
 
+    /// Get the tags of the line in a parsed form.
+    pub fn parsed_tags(&self) -> LineTags {
+        LineTags { tags: self.tags().into_iter().map(Cow::into_owned).collect() }
+    }
+
     /// Set the tags of the line to the new value.
     ///
     /// # Arguments
@@ -263,3 +495,117 @@ impl<'a> BufferLine<'a> {
         self.update_line(hashmap);
     }
 }
+
+/// The tags of a line, parsed according to the conventions Weechat and its
+/// plugins use.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineTags {
+    tags: Vec<String>,
+}
+
+impl LineTags {
+    fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.tags.iter().filter_map(move |t| t.strip_prefix(prefix))
+    }
+
+    /// Iterate over all the tags.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(|t| t.as_str())
+    }
+
+    /// Does the line have the given tag.
+    pub fn contains(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// The nick of the sender of the message, taken from the `nick_` tag.
+    pub fn nick(&self) -> Option<&str> {
+        self.with_prefix("nick_").next()
+    }
+
+    /// The host of the sender of the message, taken from the `host_` tag.
+    pub fn host(&self) -> Option<&str> {
+        self.with_prefix("host_").next()
+    }
+
+    /// The notify level the line was printed with, taken from the `notify_`
+    /// tag.
+    pub fn notify(&self) -> Option<Notify> {
+        self.tags.iter().find_map(|t| Notify::from_tag(t))
+    }
+
+    /// The level at which the line is logged, taken from the `log` tags.
+    ///
+    /// Returns 0 if the line has the `no_log` tag and `None` if the line
+    /// doesn't have a log tag.
+    pub fn log_level(&self) -> Option<u8> {
+        if self.contains("no_log") {
+            Some(0)
+        } else {
+            self.with_prefix("log").find_map(|level| level.parse().ok())
+        }
+    }
+
+    /// Was the message sent by the user, marked by the `self_msg` tag.
+    pub fn is_self_message(&self) -> bool {
+        self.contains("self_msg")
+    }
+
+    /// Is highlighting disabled for the line, marked by the `no_highlight`
+    /// tag.
+    pub fn is_highlight_disabled(&self) -> bool {
+        self.contains("no_highlight")
+    }
+
+    /// The tags the IRC plugin added to the line, without the `irc_` prefix.
+    ///
+    /// For example a line with the `irc_privmsg` tag yields `privmsg`.
+    pub fn irc(&self) -> impl Iterator<Item = &str> {
+        self.with_prefix("irc_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> LineTags {
+        LineTags { tags: tags.iter().map(|t| (*t).to_owned()).collect() }
+    }
+
+    #[test]
+    fn nick_and_host() {
+        let parsed = tags(&["irc_privmsg", "nick_alice", "host_alice@example.org"]);
+
+        assert_eq!(parsed.nick(), Some("alice"));
+        assert_eq!(parsed.host(), Some("alice@example.org"));
+        assert_eq!(tags(&["irc_privmsg"]).nick(), None);
+    }
+
+    #[test]
+    fn irc_tags_lose_their_prefix() {
+        let parsed = tags(&["irc_privmsg", "notify_message", "irc_tag_time=1"]);
+
+        assert_eq!(parsed.irc().collect::<Vec<_>>(), ["privmsg", "tag_time=1"]);
+        assert_eq!(tags(&["nick_alice"]).irc().count(), 0);
+    }
+
+    #[test]
+    fn notify_and_log_level() {
+        let parsed = tags(&["notify_highlight", "log1"]);
+
+        assert_eq!(parsed.notify(), Some(Notify::Highlight));
+        assert_eq!(parsed.log_level(), Some(1));
+        assert_eq!(tags(&["no_log", "log3"]).log_level(), Some(0));
+        assert_eq!(tags(&["nick_alice"]).log_level(), None);
+    }
+
+    #[test]
+    fn flags() {
+        let parsed = tags(&["self_msg", "no_highlight"]);
+
+        assert!(parsed.is_self_message());
+        assert!(parsed.is_highlight_disabled());
+        assert!(!tags(&[]).is_self_message());
+    }
+}
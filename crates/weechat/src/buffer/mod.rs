@@ -1,9 +1,20 @@
 //! Weechat Buffer module containing Buffer and Nick types.
 
+mod filter;
+mod history;
+mod hotlist;
+mod iter;
+mod line_builder;
 mod lines;
 mod nick;
 mod nickgroup;
+mod nicklist;
+mod property;
+mod search;
+#[cfg(feature = "async")]
+mod sender;
 mod window;
+mod writer;
 
 use std::{
     borrow::Cow,
@@ -24,13 +35,24 @@ use weechat_sys::{
     t_gui_buffer, t_gui_nick, t_hdata, t_weechat_plugin, WEECHAT_RC_ERROR, WEECHAT_RC_OK,
 };
 
+#[cfg(feature = "async")]
+pub use crate::buffer::sender::BufferSender;
 pub use crate::buffer::{
-    lines::{BufferLine, BufferLines, LineData},
+    filter::{Filter, FilterBuilder, Filters},
+    history::History,
+    hotlist::{Hotlist, HotlistCounts, HotlistEntry, HotlistPriority},
+    iter::{Buffers, Windows},
+    line_builder::{LineBuilder, Notify},
+    lines::{BufferLine, BufferLines, LineData, LineId, LineTags},
     nick::{Nick, NickSettings},
-    nickgroup::NickGroup,
-    window::Window,
+    nickgroup::{NickGroup, NickGroups, Nicks},
+    nicklist::{NickGroupSettings, NicklistState},
+    property::{BufferPointerProperty, BufferProperty, BufferPropertyValue},
+    search::{LineQuery, LineSearch, SearchDirection, SearchField},
+    window::{LayoutScope, Window},
+    writer::BufferWriter,
 };
-use crate::{LossyCString, Weechat};
+use crate::{buffer::property::PropertyType, regex::Regex, LossyCString, Weechat};
 
 /// A Weechat buffer.
 ///
@@ -146,6 +168,11 @@ impl BufferHandle {
             Ok(buffer)
         }
     }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn buffer_ptr(&self) -> *mut t_gui_buffer {
+        self.buffer_ptr.get()
+    }
 }
 
 #[cfg(feature = "async")]
@@ -257,6 +284,142 @@ pub struct BufferBuilderAsync {
     pub(crate) name: String,
     pub(crate) input_callback: Option<Box<dyn BufferInputCallbackAsync>>,
     pub(crate) close_callback: Option<Box<dyn BufferCloseCallback>>,
+    pub(crate) properties: BufferProperties,
+}
+
+/// The kind of a buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferKind {
+    /// A buffer that displays lines in a scrolling log, each line can have a
+    /// date, a prefix and a message.
+    Formatted,
+    /// A buffer with free content, every line of the buffer can be addressed
+    /// and changed using its position.
+    Free,
+}
+
+impl BufferKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BufferKind::Formatted => "formatted",
+            BufferKind::Free => "free",
+        }
+    }
+}
+
+/// Properties that are set on a buffer when it is created.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BufferProperties(Vec<(String, String)>);
+
+impl BufferProperties {
+    pub(crate) fn set(&mut self, property: &str, value: &str) {
+        match self.0.iter_mut().find(|(p, _)| p == property) {
+            Some((_, v)) => *v = value.to_owned(),
+            None => self.0.push((property.to_owned(), value.to_owned())),
+        }
+    }
+}
+
+/// Implement the setters for the initial buffer properties on a buffer
+/// builder, the builder needs a `properties` field.
+macro_rules! buffer_builder_setters {
+    () => {
+        /// Set the kind of the buffer.
+        ///
+        /// # Arguments
+        ///
+        /// * `kind` - The kind of buffer that should be created, defaults to
+        ///   `BufferKind::Formatted`.
+        pub fn kind(self, kind: BufferKind) -> Self {
+            self.property("type", kind.as_str())
+        }
+
+        /// Set a property of the buffer that should be applied when the buffer is
+        /// created.
+        ///
+        /// Setting the properties while the buffer is created avoids flickering
+        /// and signals that setting them one by one after the creation would
+        /// cause.
+        ///
+        /// # Arguments
+        ///
+        /// * `property` - The name of the property, the list of valid properties
+        ///   can be found in the Weechat [plugin API docs].
+        ///
+        /// * `value` - The value the property should get.
+        ///
+        /// [plugin API docs]: https://weechat.org/files/doc/stable/weechat_plugin_api.en.html#_buffer_set
+        pub fn property(mut self, property: &str, value: &str) -> Self {
+            self.properties.set(property, value);
+            self
+        }
+
+        /// Set the title of the buffer.
+        ///
+        /// # Arguments
+        ///
+        /// * `title` - The title the buffer should get.
+        pub fn title(self, title: &str) -> Self {
+            self.property("title", title)
+        }
+
+        /// Set the short name of the buffer.
+        ///
+        /// # Arguments
+        ///
+        /// * `short_name` - The short name the buffer should get.
+        pub fn short_name(self, short_name: &str) -> Self {
+            self.property("short_name", short_name)
+        }
+
+        /// Set a local variable of the buffer.
+        ///
+        /// # Arguments
+        ///
+        /// * `name` - The name of the local variable.
+        ///
+        /// * `value` - The value the local variable should get.
+        pub fn localvar(self, name: &str, value: &str) -> Self {
+            self.property(&format!("localvar_set_{name}"), value)
+        }
+
+        /// Enable or disable the nicklist of the buffer.
+        ///
+        /// # Arguments
+        ///
+        /// * `enable` - Should the buffer have a nicklist.
+        pub fn nicklist(self, enable: bool) -> Self {
+            self.property("nicklist", if enable { "1" } else { "0" })
+        }
+
+        /// Show or hide the time for each line of the buffer.
+        ///
+        /// # Arguments
+        ///
+        /// * `enable` - Should the time be displayed for each line.
+        pub fn time_for_each_line(self, enable: bool) -> Self {
+            self.property("time_for_each_line", if enable { "1" } else { "0" })
+        }
+
+        /// Enable or disable multiline support for the buffer input.
+        ///
+        /// # Arguments
+        ///
+        /// * `enable` - Should multiple lines be sent as a single string to the
+        ///   input callback.
+        pub fn input_multiline(self, enable: bool) -> Self {
+            self.property("input_multiline", if enable { "1" } else { "0" })
+        }
+
+        /// Create the buffer as hidden or visible.
+        ///
+        /// # Arguments
+        ///
+        /// * `hidden` - Should the buffer be hidden.
+        pub fn hidden(self, hidden: bool) -> Self {
+            self.property("hidden", if hidden { "1" } else { "0" })
+        }
+    };
 }
 
 /// Builder for the creation of a buffer.
@@ -264,6 +427,7 @@ pub struct BufferBuilder {
     pub(crate) name: String,
     pub(crate) input_callback: Option<Box<dyn BufferInputCallback>>,
     pub(crate) close_callback: Option<Box<dyn BufferCloseCallback>>,
+    pub(crate) properties: BufferProperties,
 }
 
 #[cfg(feature = "async")]
@@ -310,7 +474,12 @@ impl BufferBuilderAsync {
     /// buffer.print("Hello world");
     /// ```
     pub fn new(name: &str) -> Self {
-        BufferBuilderAsync { name: name.to_owned(), input_callback: None, close_callback: None }
+        BufferBuilderAsync {
+            name: name.to_owned(),
+            input_callback: None,
+            close_callback: None,
+            properties: BufferProperties::default(),
+        }
     }
 
     /// Set the buffer input callback.
@@ -335,9 +504,22 @@ impl BufferBuilderAsync {
         self
     }
 
+    buffer_builder_setters!();
+
     /// Build the configured buffer.
     pub fn build(self) -> Result<BufferHandle, ()> {
-        Weechat::buffer_new_with_async(self)
+        Weechat::buffer_new_with_async(self, false)
+    }
+
+    /// Build the configured buffer or adopt an existing one.
+    ///
+    /// If a buffer with the configured name already exists for this plugin,
+    /// for example after the plugin was reloaded or Weechat was upgraded, the
+    /// callbacks will be attached to the existing buffer instead of creating a
+    /// new one. This preserves the contents of the existing buffer, the
+    /// configured properties are applied to the existing buffer as well.
+    pub fn build_or_adopt(self) -> Result<BufferHandle, ()> {
+        Weechat::buffer_new_with_async(self, true)
     }
 }
 
@@ -371,6 +553,8 @@ impl BufferBuilder {
     ///     .close_callback(|weechat: &Weechat, buffer: &Buffer| {
     ///         Ok(())
     /// })
+    ///     .title("Test buffer")
+    ///     .localvar("type", "channel")
     ///     .build()
     ///     .expect("Can't create new buffer");
     ///
@@ -382,7 +566,12 @@ impl BufferBuilder {
     /// buffer.print("Hello world");
     /// ```
     pub fn new(name: &str) -> Self {
-        BufferBuilder { name: name.to_owned(), input_callback: None, close_callback: None }
+        BufferBuilder {
+            name: name.to_owned(),
+            input_callback: None,
+            close_callback: None,
+            properties: BufferProperties::default(),
+        }
     }
 
     /// Set the buffer input callback.
@@ -406,9 +595,30 @@ impl BufferBuilder {
         self
     }
 
+    buffer_builder_setters!();
+
     /// Build the configured buffer.
     pub fn build(self) -> Result<BufferHandle, ()> {
-        Weechat::buffer_new(self)
+        Weechat::buffer_new(self, false)
+    }
+
+    /// Build the configured buffer or adopt an existing one.
+    ///
+    /// If a buffer with the configured name already exists for this plugin,
+    /// for example after the plugin was reloaded or Weechat was upgraded, the
+    /// callbacks will be attached to the existing buffer instead of creating a
+    /// new one. This preserves the contents of the existing buffer, the
+    /// configured properties are applied to the existing buffer as well.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::buffer::BufferBuilder;
+    /// let buffer_handle = BufferBuilder::new("test_buffer")
+    ///     .build_or_adopt()
+    ///     .expect("Can't create or adopt the buffer");
+    /// ```
+    pub fn build_or_adopt(self) -> Result<BufferHandle, ()> {
+        Weechat::buffer_new(self, true)
     }
 }
 
@@ -475,8 +685,175 @@ impl Weechat {
         }
     }
 
+    /// Get an iterator over all the buffers of Weechat.
+    ///
+    /// The buffers are returned in the order of their number, the iterator
+    /// can be narrowed down by plugin, local variables or the buffer kind.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::Weechat;
+    /// # let weechat = unsafe { Weechat::weechat() };
+    /// for buffer in weechat.buffers().plugin("irc").localvar("type", "channel") {
+    ///     Weechat::print(&format!("Channel {}", buffer.short_name()));
+    /// }
+    /// ```
+    pub fn buffers(&self) -> Buffers<'_> {
+        Buffers::new(self)
+    }
+
+    /// Get an iterator over all the windows of Weechat.
+    pub fn windows(&self) -> Windows<'_> {
+        Windows::new(self)
+    }
+
+    /// Get an iterator over the entries of the hotlist.
+    ///
+    /// The entries are returned in the order they are displayed in the
+    /// hotlist.
+    pub fn hotlist(&self) -> Hotlist<'_> {
+        Hotlist::new(self)
+    }
+
+    /// Search buffers whose full name matches a regular expression.
+    ///
+    /// Returns an error if the regular expression isn't valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex` - A POSIX extended regular expression the full name of the
+    ///   buffer needs to match, if the regular expression starts with (?i),
+    ///   the search is case insensitive.
+    pub fn buffer_search_regex(&self, regex: &str) -> Result<Buffers<'_>, ()> {
+        let regex = Regex::new(self, regex)?;
+        Ok(Buffers::new(self).full_name_regex(regex))
+    }
+
+    /// Search for a buffer with the given name that belongs to this plugin.
+    fn buffer_search_own(&self, name: &str) -> *mut t_gui_buffer {
+        let plugin_get_name = self.get().plugin_get_name.unwrap();
+        let buffer_search = self.get().buffer_search.unwrap();
+
+        let name = LossyCString::new(name);
+
+        unsafe { buffer_search(plugin_get_name(self.ptr), name.as_ptr()) }
+    }
+
+    /// Create a new buffer with the given initial properties.
+    #[cfg(weechat430)]
+    fn buffer_create(
+        &self,
+        name: &str,
+        properties: &BufferProperties,
+        input_cb: Option<WeechatInputCbT>,
+        close_cb: WeechatCloseCbT,
+        pointers: *const c_void,
+    ) -> *mut t_gui_buffer {
+        let buffer_new_props = self.get().buffer_new_props.unwrap();
+        let hashtable_free = self.get().hashtable_free.unwrap();
+
+        let name = LossyCString::new(name);
+        let properties = properties.0.iter().map(|(p, v)| (p.as_str(), v.as_str())).collect();
+        let properties = self.hashmap_to_weechat(properties);
+
+        unsafe {
+            let buf_ptr = buffer_new_props(
+                self.ptr,
+                name.as_ptr(),
+                properties,
+                input_cb,
+                pointers,
+                ptr::null_mut(),
+                Some(close_cb),
+                pointers,
+                ptr::null_mut(),
+            );
+
+            hashtable_free(properties);
+
+            buf_ptr
+        }
+    }
+
+    /// Create a new buffer with the given initial properties.
+    ///
+    /// Weechat versions before 4.3.0 don't support setting properties while
+    /// the buffer is created, the properties are set after the buffer is
+    /// created instead.
+    #[cfg(not(weechat430))]
+    fn buffer_create(
+        &self,
+        name: &str,
+        properties: &BufferProperties,
+        input_cb: Option<WeechatInputCbT>,
+        close_cb: WeechatCloseCbT,
+        pointers: *const c_void,
+    ) -> *mut t_gui_buffer {
+        let buffer_new = self.get().buffer_new.unwrap();
+
+        let name = LossyCString::new(name);
+
+        unsafe {
+            let buf_ptr = buffer_new(
+                self.ptr,
+                name.as_ptr(),
+                input_cb,
+                pointers,
+                ptr::null_mut(),
+                Some(close_cb),
+                pointers,
+                ptr::null_mut(),
+            );
+
+            if !buf_ptr.is_null() {
+                self.buffer_set_properties(buf_ptr, properties);
+            }
+
+            buf_ptr
+        }
+    }
+
+    /// Set the given properties on an existing buffer.
+    fn buffer_set_properties(&self, buffer: *mut t_gui_buffer, properties: &BufferProperties) {
+        let buffer_set = self.get().buffer_set.unwrap();
+
+        for (property, value) in &properties.0 {
+            let property = LossyCString::new(property);
+            let value = LossyCString::new(value);
+            unsafe { buffer_set(buffer, property.as_ptr(), value.as_ptr()) };
+        }
+    }
+
+    /// Replace the input and close callbacks of an existing buffer.
+    fn buffer_set_callbacks(
+        &self,
+        buffer: *mut t_gui_buffer,
+        input_cb: Option<WeechatInputCbT>,
+        close_cb: WeechatCloseCbT,
+        pointers: *const c_void,
+    ) {
+        let buffer_set_pointer = self.get().buffer_set_pointer.unwrap();
+
+        let input_cb = input_cb.map_or(ptr::null_mut(), |cb| cb as *mut c_void);
+
+        let properties = [
+            ("input_callback", input_cb),
+            ("input_callback_pointer", pointers as *mut c_void),
+            ("input_callback_data", ptr::null_mut()),
+            ("close_callback", close_cb as *mut c_void),
+            ("close_callback_pointer", pointers as *mut c_void),
+            ("close_callback_data", ptr::null_mut()),
+        ];
+
+        for (property, pointer) in properties {
+            let property = LossyCString::new(property);
+            unsafe { buffer_set_pointer(buffer, property.as_ptr(), pointer) };
+        }
+    }
+
     #[cfg(feature = "async")]
-    fn buffer_new_with_async(builder: BufferBuilderAsync) -> Result<BufferHandle, ()> {
+    fn buffer_new_with_async(builder: BufferBuilderAsync, adopt: bool) -> Result<BufferHandle, ()> {
         unsafe extern "C" fn c_input_cb(
             pointer: *const c_void,
             _data: *mut c_void,
@@ -535,6 +912,9 @@ impl Weechat {
                 .expect("Buffer cell wasn't initialized properly")
                 .replace(ptr::null_mut());
 
+            // Cancel the tasks that were bound to the buffer.
+            crate::task_group::cancel_buffer_tasks(buffer.ptr());
+
             if ret {
                 WEECHAT_RC_OK
             } else {
@@ -562,20 +942,26 @@ impl Weechat {
 
         let buffer_pointers_ref = Box::leak(buffer_pointers);
 
-        let buf_new = weechat.get().buffer_new.unwrap();
-        let c_name = LossyCString::new(builder.name);
+        let buf_ptr =
+            if adopt { weechat.buffer_search_own(&builder.name) } else { ptr::null_mut() };
 
-        let buf_ptr = unsafe {
-            buf_new(
-                weechat.ptr,
-                c_name.as_ptr(),
+        let buf_ptr = if buf_ptr.is_null() {
+            weechat.buffer_create(
+                &builder.name,
+                &builder.properties,
                 c_input_cb,
+                c_close_cb,
                 buffer_pointers_ref as *const _ as *const c_void,
-                ptr::null_mut(),
-                Some(c_close_cb),
-                buffer_pointers_ref as *const _ as *const c_void,
-                ptr::null_mut(),
             )
+        } else {
+            weechat.buffer_set_callbacks(
+                buf_ptr,
+                c_input_cb,
+                c_close_cb,
+                buffer_pointers_ref as *const _ as *const c_void,
+            );
+            weechat.buffer_set_properties(buf_ptr, &builder.properties);
+            buf_ptr
         };
 
         if buf_ptr.is_null() {
@@ -599,7 +985,7 @@ impl Weechat {
         })
     }
 
-    fn buffer_new(builder: BufferBuilder) -> Result<BufferHandle, ()> {
+    fn buffer_new(builder: BufferBuilder, adopt: bool) -> Result<BufferHandle, ()> {
         unsafe extern "C" fn c_input_cb(
             pointer: *const c_void,
             _data: *mut c_void,
@@ -651,6 +1037,10 @@ impl Weechat {
                 .expect("Buffer cell wasn't initialized properly")
                 .replace(ptr::null_mut());
 
+            // Cancel the tasks that were bound to the buffer.
+            #[cfg(feature = "async")]
+            crate::task_group::cancel_buffer_tasks(buffer.ptr());
+
             if ret {
                 WEECHAT_RC_OK
             } else {
@@ -677,20 +1067,26 @@ impl Weechat {
         });
         let buffer_pointers_ref = Box::leak(buffer_pointers);
 
-        let buf_new = weechat.get().buffer_new.unwrap();
-        let c_name = LossyCString::new(builder.name);
+        let buf_ptr =
+            if adopt { weechat.buffer_search_own(&builder.name) } else { ptr::null_mut() };
 
-        let buf_ptr = unsafe {
-            buf_new(
-                weechat.ptr,
-                c_name.as_ptr(),
+        let buf_ptr = if buf_ptr.is_null() {
+            weechat.buffer_create(
+                &builder.name,
+                &builder.properties,
                 c_input_cb,
+                c_close_cb,
                 buffer_pointers_ref as *const _ as *const c_void,
-                ptr::null_mut(),
-                Some(c_close_cb),
-                buffer_pointers_ref as *const _ as *const c_void,
-                ptr::null_mut(),
             )
+        } else {
+            weechat.buffer_set_callbacks(
+                buf_ptr,
+                c_input_cb,
+                c_close_cb,
+                buffer_pointers_ref as *const _ as *const c_void,
+            );
+            weechat.buffer_set_properties(buf_ptr, &builder.properties);
+            buf_ptr
         };
 
         if buf_ptr.is_null() {
@@ -722,6 +1118,12 @@ pub(crate) type WeechatInputCbT = unsafe extern "C" fn(
     input_data: *const c_char,
 ) -> c_int;
 
+pub(crate) type WeechatCloseCbT = unsafe extern "C" fn(
+    pointer: *const c_void,
+    data: *mut c_void,
+    buffer: *mut t_gui_buffer,
+) -> c_int;
+
 impl Buffer<'_> {
     fn weechat(&self) -> &Weechat {
         match &self.inner {
@@ -757,9 +1159,9 @@ impl Buffer<'_> {
     pub fn print(&self, message: &str) {
         let weechat = self.weechat();
 
-        #[cfg(not(weechat410))]
+        #[cfg(weechat420)]
         let printf_date_tags = weechat.get().printf_datetime_tags.unwrap();
-        #[cfg(weechat410)]
+        #[cfg(not(weechat420))]
         let printf_date_tags = weechat.get().printf_date_tags.unwrap();
 
         let fmt_str = LossyCString::new("%s");
@@ -769,7 +1171,7 @@ impl Buffer<'_> {
             printf_date_tags(
                 self.ptr(),
                 0,
-                #[cfg(not(weechat410))]
+                #[cfg(weechat420)]
                 0,
                 ptr::null(),
                 fmt_str.as_ptr(),
@@ -791,9 +1193,9 @@ impl Buffer<'_> {
     pub fn print_date_tags(&self, date: isize, tags: &[&str], message: &str) {
         let weechat = self.weechat();
 
-        #[cfg(not(weechat410))]
+        #[cfg(weechat420)]
         let printf_date_tags = weechat.get().printf_datetime_tags.unwrap();
-        #[cfg(weechat410)]
+        #[cfg(not(weechat420))]
         let printf_date_tags = weechat.get().printf_date_tags.unwrap();
 
         let fmt_str = LossyCString::new("%s");
@@ -805,7 +1207,7 @@ impl Buffer<'_> {
             printf_date_tags(
                 self.ptr(),
                 date as _,
-                #[cfg(not(weechat410))]
+                #[cfg(weechat420)]
                 0,
                 tags.as_ptr(),
                 fmt_str.as_ptr(),
@@ -814,6 +1216,71 @@ impl Buffer<'_> {
         }
     }
 
+    /// Display a message on the given line of a buffer with free content.
+    ///
+    /// Lines that don't exist yet will be created, the number of lines the
+    /// buffer has can be fetched using the [`num_lines()`] method.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - The line number, the first line is 0. A negative value adds a
+    ///   line after the last line, for example -1 is the line immediately
+    ///   after the last line.
+    ///
+    /// * `message` - The message that will be displayed.
+    ///
+    /// [`num_lines()`]: Buffer::num_lines
+    pub fn print_y(&self, y: i32, message: &str) {
+        let weechat = self.weechat();
+
+        #[cfg(weechat420)]
+        let printf_y_date_tags = weechat.get().printf_y_datetime_tags.unwrap();
+        #[cfg(not(weechat420))]
+        let printf_y_date_tags = weechat.get().printf_y_date_tags.unwrap();
+
+        let fmt_str = LossyCString::new("%s");
+        let message = LossyCString::new(message);
+
+        unsafe {
+            printf_y_date_tags(
+                self.ptr(),
+                y,
+                0,
+                #[cfg(weechat420)]
+                0,
+                ptr::null(),
+                fmt_str.as_ptr(),
+                message.as_ptr(),
+            )
+        }
+    }
+
+    /// Clear the content of the given line of a buffer with free content.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - The line number of the line that should be cleared, the first
+    ///   line is 0.
+    pub fn clear_line(&self, y: i32) {
+        self.print_y(y, "");
+    }
+
+    /// Get the kind of the buffer.
+    pub fn kind(&self) -> BufferKind {
+        match self.get_integer("type") {
+            1 => BufferKind::Free,
+            _ => BufferKind::Formatted,
+        }
+    }
+
+    /// Get an iterator over the top level groups of the nicklist.
+    ///
+    /// The nicks and subgroups of every group can be iterated over using
+    /// [`NickGroup::nicks`] and [`NickGroup::subgroups`].
+    pub fn nicklist_groups(&self) -> NickGroups<'_> {
+        NickGroups::new(self.weechat().ptr, self.ptr(), None)
+    }
+
     /// Search for a nicklist group by name
     ///
     /// # Arguments
@@ -1069,6 +1536,94 @@ impl Buffer<'_> {
         unsafe { buffer_get(self.ptr(), property.as_ptr()) }
     }
 
+    /// Get the value of a buffer property.
+    ///
+    /// Returns `None` if the property can't be read or if Weechat doesn't
+    /// have a value for the string property.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The property for which the value should be fetched.
+    pub fn get_property(&self, property: BufferProperty) -> Option<BufferPropertyValue<'_>> {
+        if !property.is_readable() {
+            return None;
+        }
+
+        match property.property_type() {
+            PropertyType::Integer => {
+                Some(BufferPropertyValue::Integer(self.get_integer(property.name())))
+            }
+            PropertyType::String => {
+                self.get_string(property.name()).map(BufferPropertyValue::String)
+            }
+        }
+    }
+
+    /// Set the value of a buffer property.
+    ///
+    /// Returns an error if the property can't be modified or if the type of
+    /// the value doesn't match the type of the property.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The property that should be set.
+    ///
+    /// * `value` - The value that the property should get.
+    pub fn set_property<'v>(
+        &self,
+        property: BufferProperty,
+        value: impl Into<BufferPropertyValue<'v>>,
+    ) -> Result<(), ()> {
+        if !property.is_writable() {
+            return Err(());
+        }
+
+        match (property.property_type(), value.into()) {
+            // The type is read as an integer but it's set using its name.
+            (PropertyType::Integer, BufferPropertyValue::Integer(value))
+                if property == BufferProperty::Type =>
+            {
+                let kind = match value {
+                    0 => BufferKind::Formatted,
+                    1 => BufferKind::Free,
+                    _ => return Err(()),
+                };
+                self.set(property.name(), kind.as_str())
+            }
+            (PropertyType::Integer, BufferPropertyValue::Integer(value)) => {
+                self.set(property.name(), &value.to_string())
+            }
+            (PropertyType::String, BufferPropertyValue::String(value)) => {
+                self.set(property.name(), &value)
+            }
+            _ => return Err(()),
+        }
+
+        Ok(())
+    }
+
+    /// Get the value of a buffer pointer property.
+    ///
+    /// Returns a null pointer if the buffer doesn't have a value for the
+    /// property.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The property that should be fetched.
+    ///
+    /// # Safety
+    ///
+    /// The returned pointer is owned by Weechat. It's only valid until the
+    /// property changes or the buffer is closed, and it must not be freed.
+    pub unsafe fn get_pointer_property(&self, property: BufferPointerProperty) -> *mut c_void {
+        let weechat = self.weechat();
+
+        let buffer_get = weechat.get().buffer_get_pointer.unwrap();
+        let property = LossyCString::new(property.name());
+
+        unsafe { buffer_get(self.ptr(), property.as_ptr()) }
+    }
+
     /// Get the value of a buffer localvar
     ///
     /// # Arguments
@@ -1163,8 +1718,10 @@ impl Buffer<'_> {
     }
 
     /// Get the title of the buffer
-    pub fn title(&self) {
-        self.get_string("title");
+    ///
+    /// Returns `None` if no title has been set.
+    pub fn title(&self) -> Option<Cow<'_, str>> {
+        self.get_string("title")
     }
 
     /// Set the title of the buffer.
@@ -1176,6 +1733,23 @@ impl Buffer<'_> {
         self.set("title", title);
     }
 
+    /// Add the buffer to the hotlist.
+    ///
+    /// If the buffer already is in the hotlist the priority of the entry is
+    /// raised if needed and the count of messages for the priority increased.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority of the message the buffer is added for.
+    pub fn set_hotlist(&self, priority: HotlistPriority) {
+        self.set("hotlist", priority.as_str());
+    }
+
+    /// Remove the buffer from the hotlist.
+    pub fn clear_hotlist(&self) {
+        self.set("hotlist", "-1");
+    }
+
     /// Disable logging for this buffer.
     pub fn disable_log(&self) {
         self.set("localvar_set_no_log", "1");
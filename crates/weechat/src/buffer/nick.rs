@@ -5,6 +5,7 @@ use weechat_sys::{t_gui_buffer, t_gui_nick, t_weechat_plugin};
 use crate::{buffer::Buffer, LossyCString, Weechat};
 
 /// Settings to create a new nick.
+#[derive(Debug, Clone)]
 pub struct NickSettings<'a> {
     /// Name of the new nick.
     pub(crate) name: &'a str,
@@ -122,4 +123,67 @@ impl Nick<'_> {
     pub fn prefix_color(&self) -> Cow<'_, str> {
         self.get_string("prefix_color").unwrap()
     }
+
+    /// Is the nick visible in the nicklist.
+    pub fn visible(&self) -> bool {
+        let weechat = self.get_weechat();
+        let get_integer = weechat.get().nicklist_nick_get_integer.unwrap();
+        let property = LossyCString::new("visible");
+
+        unsafe { get_integer(self.buf_ptr, self.ptr, property.as_ptr()) != 0 }
+    }
+
+    fn set(&self, property: &str, value: &str) {
+        let weechat = self.get_weechat();
+        let nick_set = weechat.get().nicklist_nick_set.unwrap();
+
+        let property = LossyCString::new(property);
+        let value = LossyCString::new(value);
+
+        unsafe { nick_set(self.buf_ptr, self.ptr, property.as_ptr(), value.as_ptr()) };
+    }
+
+    /// Set the color of the nick.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The new color of the nick.
+    pub fn set_color(&self, color: &str) {
+        self.set("color", color);
+    }
+
+    /// Set the prefix of the nick.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix displayed before the nick in the nicklist.
+    pub fn set_prefix(&self, prefix: &str) {
+        self.set("prefix", prefix);
+    }
+
+    /// Set the color of the nick prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix_color` - The new color of the prefix.
+    pub fn set_prefix_color(&self, prefix_color: &str) {
+        self.set("prefix_color", prefix_color);
+    }
+
+    /// Set the visibility of the nick.
+    ///
+    /// # Arguments
+    ///
+    /// * `visible` - Should the nick be visible in the nicklist.
+    pub fn set_visible(&self, visible: bool) {
+        self.set("visible", if visible { "1" } else { "0" });
+    }
+
+    /// Remove the nick from the nicklist.
+    pub fn remove(self) {
+        let weechat = self.get_weechat();
+        let nicklist_remove_nick = weechat.get().nicklist_remove_nick.unwrap();
+
+        unsafe { nicklist_remove_nick(self.buf_ptr, self.ptr) };
+    }
 }
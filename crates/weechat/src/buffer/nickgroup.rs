@@ -1,6 +1,6 @@
-use std::{borrow::Cow, ffi::CStr, marker::PhantomData};
+use std::{borrow::Cow, ffi::CStr, marker::PhantomData, os::raw::c_void, ptr};
 
-use weechat_sys::{t_gui_buffer, t_gui_nick_group, t_weechat_plugin};
+use weechat_sys::{t_gui_buffer, t_gui_nick, t_gui_nick_group, t_hdata, t_weechat_plugin};
 
 use crate::{
     buffer::{Buffer, Nick, NickSettings},
@@ -61,6 +61,54 @@ impl NickGroup<'_> {
         self.get_integer("visible") != 0
     }
 
+    fn set(&self, property: &str, value: &str) {
+        let weechat = self.get_weechat();
+        let group_set = weechat.get().nicklist_group_set.unwrap();
+
+        let property = LossyCString::new(property);
+        let value = LossyCString::new(value);
+
+        unsafe { group_set(self.buf_ptr, self.ptr, property.as_ptr(), value.as_ptr()) };
+    }
+
+    /// Set the color of the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The new color of the group.
+    pub fn set_color(&self, color: &str) {
+        self.set("color", color);
+    }
+
+    /// Set the visibility of the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `visible` - Should the group be visible in the nicklist.
+    pub fn set_visible(&self, visible: bool) {
+        self.set("visible", if visible { "1" } else { "0" });
+    }
+
+    /// Remove the group, all its subgroups and all its nicks from the
+    /// nicklist.
+    pub fn remove(self) {
+        let weechat = self.get_weechat();
+        let nicklist_remove_group = weechat.get().nicklist_remove_group.unwrap();
+
+        unsafe { nicklist_remove_group(self.buf_ptr, self.ptr) };
+    }
+
+    /// Get an iterator over the nicks that are directly in this group.
+    pub fn nicks(&self) -> Nicks<'_> {
+        Nicks::new(self.weechat_ptr, self.buf_ptr, self.ptr)
+    }
+
+    /// Get an iterator over the groups that are direct children of this
+    /// group.
+    pub fn subgroups(&self) -> NickGroups<'_> {
+        NickGroups::new(self.weechat_ptr, self.buf_ptr, Some(self.ptr))
+    }
+
     /// Get the group nesting level.
     ///
     /// Returns 0 if this is the root group, 1 if it's a child of the root
@@ -117,3 +165,176 @@ impl NickGroup<'_> {
         }
     }
 }
+
+/// A position in the nicklist of a buffer, the nicklist is walked depth first
+/// starting with the root group.
+pub(crate) struct NicklistCursor {
+    pub(crate) weechat_ptr: *mut t_weechat_plugin,
+    pub(crate) buf_ptr: *mut t_gui_buffer,
+    pub(crate) group: *mut t_gui_nick_group,
+    pub(crate) nick: *mut t_gui_nick,
+    done: bool,
+}
+
+impl NicklistCursor {
+    pub(crate) fn new(weechat_ptr: *mut t_weechat_plugin, buf_ptr: *mut t_gui_buffer) -> Self {
+        NicklistCursor {
+            weechat_ptr,
+            buf_ptr,
+            group: ptr::null_mut(),
+            nick: ptr::null_mut(),
+            done: false,
+        }
+    }
+
+    /// Move to the next item of the nicklist, returns false once the end of
+    /// the nicklist is reached.
+    pub(crate) fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+
+        let weechat = Weechat::from_ptr(self.weechat_ptr);
+        let get_next_item = weechat.get().nicklist_get_next_item.unwrap();
+
+        unsafe { get_next_item(self.buf_ptr, &mut self.group, &mut self.nick) };
+
+        self.done = self.group.is_null() && self.nick.is_null();
+        !self.done
+    }
+
+    pub(crate) fn group_parent(&self, group: *mut t_gui_nick_group) -> *mut t_gui_nick_group {
+        let weechat = Weechat::from_ptr(self.weechat_ptr);
+        let get_pointer = weechat.get().nicklist_group_get_pointer.unwrap();
+        let property = LossyCString::new("parent");
+
+        unsafe { get_pointer(self.buf_ptr, group, property.as_ptr()) as _ }
+    }
+
+    pub(crate) fn nick_group(&self, nick: *mut t_gui_nick) -> *mut t_gui_nick_group {
+        let weechat = Weechat::from_ptr(self.weechat_ptr);
+        let get_pointer = weechat.get().nicklist_nick_get_pointer.unwrap();
+        let property = LossyCString::new("group");
+
+        unsafe { get_pointer(self.buf_ptr, nick, property.as_ptr()) as _ }
+    }
+}
+
+/// An iterator over the child groups of a nicklist group.
+///
+/// Nicks and groups must not be removed while the iterator is in use, collect
+/// the items first if the nicklist needs to be modified.
+pub struct NickGroups<'a> {
+    weechat_ptr: *mut t_weechat_plugin,
+    buf_ptr: *mut t_gui_buffer,
+    hdata: *mut t_hdata,
+    current: *mut c_void,
+    buffer: PhantomData<&'a Buffer<'a>>,
+}
+
+impl NickGroups<'_> {
+    /// Create an iterator over the children of the given group, if no group
+    /// is given the children of the root group are returned.
+    pub(crate) fn new(
+        weechat_ptr: *mut t_weechat_plugin,
+        buf_ptr: *mut t_gui_buffer,
+        parent: Option<*mut t_gui_nick_group>,
+    ) -> Self {
+        let weechat = Weechat::from_ptr(weechat_ptr);
+
+        let (hdata, current) = unsafe {
+            let hdata = weechat.hdata_get("nick_group");
+
+            let parent = match parent {
+                Some(parent) => parent as *mut c_void,
+                None => {
+                    let buffer_hdata = weechat.hdata_get("buffer");
+                    weechat.hdata_pointer(buffer_hdata, buf_ptr as _, "nicklist_root")
+                }
+            };
+
+            let current = if parent.is_null() {
+                ptr::null_mut()
+            } else {
+                weechat.hdata_pointer(hdata, parent, "children")
+            };
+
+            (hdata, current)
+        };
+
+        NickGroups { weechat_ptr, buf_ptr, hdata, current, buffer: PhantomData }
+    }
+}
+
+impl<'a> Iterator for NickGroups<'a> {
+    type Item = NickGroup<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            return None;
+        }
+
+        let group = self.current;
+        let weechat = Weechat::from_ptr(self.weechat_ptr);
+        self.current = unsafe { weechat.hdata_move(self.hdata, group, 1) };
+
+        Some(NickGroup {
+            ptr: group as _,
+            buf_ptr: self.buf_ptr,
+            weechat_ptr: self.weechat_ptr,
+            buffer: PhantomData,
+        })
+    }
+}
+
+/// An iterator over the nicks of a nicklist group.
+///
+/// Nicks and groups must not be removed while the iterator is in use, collect
+/// the items first if the nicklist needs to be modified.
+pub struct Nicks<'a> {
+    weechat_ptr: *mut t_weechat_plugin,
+    buf_ptr: *mut t_gui_buffer,
+    hdata: *mut t_hdata,
+    current: *mut c_void,
+    buffer: PhantomData<&'a Buffer<'a>>,
+}
+
+impl Nicks<'_> {
+    fn new(
+        weechat_ptr: *mut t_weechat_plugin,
+        buf_ptr: *mut t_gui_buffer,
+        group: *mut t_gui_nick_group,
+    ) -> Self {
+        let weechat = Weechat::from_ptr(weechat_ptr);
+
+        let (hdata, current) = unsafe {
+            let group_hdata = weechat.hdata_get("nick_group");
+            let current = weechat.hdata_pointer(group_hdata, group as _, "nicks");
+
+            (weechat.hdata_get("nick"), current)
+        };
+
+        Nicks { weechat_ptr, buf_ptr, hdata, current, buffer: PhantomData }
+    }
+}
+
+impl<'a> Iterator for Nicks<'a> {
+    type Item = Nick<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            return None;
+        }
+
+        let nick = self.current;
+        let weechat = Weechat::from_ptr(self.weechat_ptr);
+        self.current = unsafe { weechat.hdata_move(self.hdata, nick, 1) };
+
+        Some(Nick {
+            ptr: nick as _,
+            buf_ptr: self.buf_ptr,
+            weechat_ptr: self.weechat_ptr,
+            buffer: PhantomData,
+        })
+    }
+}
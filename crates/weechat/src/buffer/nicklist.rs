@@ -0,0 +1,336 @@
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+
+use weechat_sys::{t_gui_nick, t_gui_nick_group};
+
+use crate::buffer::{nickgroup::NicklistCursor, Buffer, Nick, NickGroup, NickSettings};
+
+/// Settings for a nicklist group that should be part of a nicklist.
+#[derive(Debug, Clone)]
+pub struct NickGroupSettings<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) color: &'a str,
+    pub(crate) visible: bool,
+    pub(crate) parent: Option<&'a str>,
+}
+
+impl<'a> NickGroupSettings<'a> {
+    /// Create new nicklist group settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the group.
+    pub fn new(name: &str) -> NickGroupSettings<'_> {
+        NickGroupSettings { name, color: "", visible: true, parent: None }
+    }
+
+    /// Set the color of the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color that the group should have.
+    pub fn set_color(mut self, color: &'a str) -> NickGroupSettings<'a> {
+        self.color = color;
+        self
+    }
+
+    /// Set the visibility of the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `visible` - Should the group be visible in the nicklist. Defaults to
+    ///   `true`.
+    pub fn set_visible(mut self, visible: bool) -> NickGroupSettings<'a> {
+        self.visible = visible;
+        self
+    }
+
+    /// Set the parent of the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The name of the parent group, the parent group needs to
+    ///   be added to the nicklist state before this group. Groups without a
+    ///   parent are added to the root group.
+    pub fn set_parent(mut self, parent: &'a str) -> NickGroupSettings<'a> {
+        self.parent = Some(parent);
+        self
+    }
+}
+
+/// The desired content of a nicklist.
+///
+/// The state can be applied to a buffer using [`Buffer::sync_nicklist`].
+#[derive(Default)]
+pub struct NicklistState<'a> {
+    groups: Vec<NickGroupSettings<'a>>,
+    nicks: Vec<(Option<&'a str>, NickSettings<'a>)>,
+}
+
+impl<'a> NicklistState<'a> {
+    /// Create a new empty nicklist state.
+    pub fn new() -> Self {
+        NicklistState::default()
+    }
+
+    /// Add a group to the nicklist state.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The settings of the group.
+    pub fn add_group(&mut self, group: NickGroupSettings<'a>) {
+        self.groups.push(group);
+    }
+
+    /// Add a nick to the nicklist state.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The name of the group the nick should be in, if no group
+    ///   is given the nick is added to the root group.
+    ///
+    /// * `nick` - The settings of the nick.
+    pub fn add_nick(&mut self, group: Option<&'a str>, nick: NickSettings<'a>) {
+        self.nicks.push((group, nick));
+    }
+}
+
+/// A snapshot of the nicklist of a buffer.
+struct CurrentNicklist {
+    root: *mut t_gui_nick_group,
+    groups: HashMap<String, (*mut t_gui_nick_group, *mut t_gui_nick_group)>,
+    nicks: HashMap<String, (*mut t_gui_nick, *mut t_gui_nick_group)>,
+}
+
+impl Buffer<'_> {
+    fn nick_group_from_ptr(&self, ptr: *mut t_gui_nick_group) -> NickGroup<'_> {
+        NickGroup { ptr, buf_ptr: self.ptr(), weechat_ptr: self.weechat().ptr, buffer: PhantomData }
+    }
+
+    fn nick_from_ptr(&self, ptr: *mut t_gui_nick) -> Nick<'_> {
+        Nick { ptr, buf_ptr: self.ptr(), weechat_ptr: self.weechat().ptr, buffer: PhantomData }
+    }
+
+    fn scan_nicklist(&self) -> CurrentNicklist {
+        let mut cursor = NicklistCursor::new(self.weechat().ptr, self.ptr());
+        let mut current = CurrentNicklist {
+            root: std::ptr::null_mut(),
+            groups: HashMap::new(),
+            nicks: HashMap::new(),
+        };
+
+        while cursor.advance() {
+            if !cursor.nick.is_null() {
+                let name = self.nick_from_ptr(cursor.nick).name().into_owned();
+                current.nicks.insert(name, (cursor.nick, cursor.nick_group(cursor.nick)));
+            } else {
+                let parent = cursor.group_parent(cursor.group);
+
+                if parent.is_null() {
+                    current.root = cursor.group;
+                } else {
+                    let name = self.nick_group_from_ptr(cursor.group).name().into_owned();
+                    current.groups.insert(name, (cursor.group, parent));
+                }
+            }
+        }
+
+        current
+    }
+
+    /// Remove a group from the nicklist snapshot, its subgroups and nicks are
+    /// removed from the snapshot as well.
+    fn forget_group(&self, current: &mut CurrentNicklist, group: *mut t_gui_nick_group) {
+        let nick_group = self.nick_group_from_ptr(group);
+
+        for nick in nick_group.nicks() {
+            let name = nick.name();
+
+            if current.nicks.get(&*name).is_some_and(|&(ptr, _)| ptr == nick.ptr) {
+                current.nicks.remove(&*name);
+            }
+        }
+
+        for subgroup in nick_group.subgroups() {
+            self.forget_group(current, subgroup.ptr);
+        }
+
+        let name = nick_group.name();
+
+        if current.groups.get(&*name).is_some_and(|&(ptr, _)| ptr == group) {
+            current.groups.remove(&*name);
+        }
+    }
+
+    /// Remove a group from the nicklist and from the nicklist snapshot.
+    fn remove_group_from_snapshot(
+        &self,
+        current: &mut CurrentNicklist,
+        group: *mut t_gui_nick_group,
+    ) {
+        let remove_group = self.weechat().get().nicklist_remove_group.unwrap();
+
+        self.forget_group(current, group);
+        unsafe { remove_group(self.ptr(), group) };
+    }
+
+    fn add_group_from_settings(
+        &self,
+        current: &CurrentNicklist,
+        group: &NickGroupSettings,
+    ) -> Result<*mut t_gui_nick_group, ()> {
+        let parent = match group.parent {
+            Some(parent) => Some(self.nick_group_from_ptr(current.groups.get(parent).ok_or(())?.0)),
+            None => None,
+        };
+
+        self.add_nicklist_group(group.name, group.color, group.visible, parent.as_ref())
+            .map(|g| g.ptr)
+    }
+
+    /// Bring the nicklist of the buffer into the given state.
+    ///
+    /// Only the differences between the current nicklist and the given state
+    /// are applied: groups and nicks that are missing are added, ones that
+    /// aren't part of the state are removed and ones whose properties
+    /// changed are updated. Groups and nicks that moved to a different parent
+    /// are removed and added again.
+    ///
+    /// The nicklist is scanned only once, the changes are tracked while they
+    /// are applied. Weechat has no way to hold back the nicklist refresh, every
+    /// change still sends out its own nicklist signal.
+    ///
+    /// Returns an error if a group or a nick references a group that isn't
+    /// part of the state, or if a group or nick couldn't be added. The changes
+    /// that were made up to that point are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The desired state of the nicklist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::buffer::{BufferBuilder, NickGroupSettings, NickSettings, NicklistState};
+    /// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+    /// # let buffer = buffer_handle.upgrade().unwrap();
+    /// let mut state = NicklistState::new();
+    /// state.add_group(NickGroupSettings::new("000|o").set_color("weechat.color.nicklist_group"));
+    /// state.add_nick(Some("000|o"), NickSettings::new("alice").set_prefix("@"));
+    /// state.add_nick(None, NickSettings::new("bob"));
+    ///
+    /// buffer.sync_nicklist(&state).unwrap();
+    /// ```
+    pub fn sync_nicklist(&self, state: &NicklistState) -> Result<(), ()> {
+        let weechat = self.weechat();
+        let remove_nick = weechat.get().nicklist_remove_nick.unwrap();
+
+        let desired_groups: HashSet<&str> = state.groups.iter().map(|g| g.name).collect();
+        let desired_nicks: HashSet<&str> = state.nicks.iter().map(|(_, n)| n.name).collect();
+
+        let mut current = self.scan_nicklist();
+
+        let unwanted_groups: Vec<String> = current
+            .groups
+            .keys()
+            .filter(|n| !desired_groups.contains(n.as_str()))
+            .cloned()
+            .collect();
+
+        for name in unwanted_groups {
+            // The group might already be gone if one of its parents was
+            // removed.
+            if let Some(&(ptr, _)) = current.groups.get(&name) {
+                self.remove_group_from_snapshot(&mut current, ptr);
+            }
+        }
+
+        for group in &state.groups {
+            let parent = match group.parent {
+                Some(parent) => current.groups.get(parent).ok_or(())?.0,
+                None => current.root,
+            };
+
+            match current.groups.get(group.name) {
+                Some(&(ptr, current_parent)) if current_parent == parent => {
+                    let nick_group = self.nick_group_from_ptr(ptr);
+
+                    if nick_group.color() != group.color {
+                        nick_group.set_color(group.color);
+                    }
+
+                    if nick_group.visible() != group.visible {
+                        nick_group.set_visible(group.visible);
+                    }
+                }
+                Some(&(ptr, _)) => {
+                    self.remove_group_from_snapshot(&mut current, ptr);
+
+                    let ptr = self.add_group_from_settings(&current, group)?;
+                    current.groups.insert(group.name.to_owned(), (ptr, parent));
+                }
+                None => {
+                    let ptr = self.add_group_from_settings(&current, group)?;
+                    current.groups.insert(group.name.to_owned(), (ptr, parent));
+                }
+            }
+        }
+
+        current.nicks.retain(|name, &mut (ptr, _)| {
+            let keep = desired_nicks.contains(name.as_str());
+
+            if !keep {
+                unsafe { remove_nick(self.ptr(), ptr) };
+            }
+
+            keep
+        });
+
+        for (group_name, settings) in &state.nicks {
+            let group = match group_name {
+                Some(name) => current.groups.get(*name).ok_or(())?.0,
+                None => current.root,
+            };
+
+            match current.nicks.get(settings.name) {
+                Some(&(ptr, current_group)) if current_group == group => {
+                    let nick = self.nick_from_ptr(ptr);
+
+                    if nick.color() != settings.color {
+                        nick.set_color(settings.color);
+                    }
+
+                    if nick.prefix() != settings.prefix {
+                        nick.set_prefix(settings.prefix);
+                    }
+
+                    if nick.prefix_color() != settings.prefix_color {
+                        nick.set_prefix_color(settings.prefix_color);
+                    }
+
+                    if nick.visible() != settings.visible {
+                        nick.set_visible(settings.visible);
+                    }
+
+                    continue;
+                }
+                Some(&(ptr, _)) => unsafe { remove_nick(self.ptr(), ptr) },
+                None => (),
+            }
+
+            let group =
+                if group == current.root { None } else { Some(self.nick_group_from_ptr(group)) };
+
+            let nick =
+                Buffer::add_nick_helper(weechat, self.ptr(), settings.clone(), group.as_ref());
+
+            if nick.is_null() {
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+}
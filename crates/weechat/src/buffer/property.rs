@@ -0,0 +1,384 @@
+use std::borrow::Cow;
+
+use crate::buffer::BufferKind;
+
+/// The value of a buffer property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BufferPropertyValue<'a> {
+    /// The value of an integer property, boolean properties are represented
+    /// as 0 or 1.
+    Integer(i32),
+    /// The value of a string property.
+    String(Cow<'a, str>),
+}
+
+impl BufferPropertyValue<'_> {
+    /// Get the value as an integer.
+    ///
+    /// Returns `None` if this is the value of a string property.
+    pub fn as_integer(&self) -> Option<i32> {
+        match self {
+            BufferPropertyValue::Integer(value) => Some(*value),
+            BufferPropertyValue::String(_) => None,
+        }
+    }
+
+    /// Get the value as a boolean.
+    ///
+    /// Returns `None` if this is the value of a string property.
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_integer().map(|value| value != 0)
+    }
+
+    /// Get the value as a string.
+    ///
+    /// Returns `None` if this is the value of an integer property.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BufferPropertyValue::Integer(_) => None,
+            BufferPropertyValue::String(value) => Some(value),
+        }
+    }
+}
+
+impl From<i32> for BufferPropertyValue<'_> {
+    fn from(value: i32) -> Self {
+        BufferPropertyValue::Integer(value)
+    }
+}
+
+impl From<bool> for BufferPropertyValue<'_> {
+    fn from(value: bool) -> Self {
+        BufferPropertyValue::Integer(value as i32)
+    }
+}
+
+impl<'a> From<&'a str> for BufferPropertyValue<'a> {
+    fn from(value: &'a str) -> Self {
+        BufferPropertyValue::String(Cow::from(value))
+    }
+}
+
+impl From<String> for BufferPropertyValue<'_> {
+    fn from(value: String) -> Self {
+        BufferPropertyValue::String(Cow::from(value))
+    }
+}
+
+impl From<BufferKind> for BufferPropertyValue<'_> {
+    fn from(value: BufferKind) -> Self {
+        match value {
+            BufferKind::Formatted => BufferPropertyValue::Integer(0),
+            BufferKind::Free => BufferPropertyValue::Integer(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PropertyType {
+    Integer,
+    String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+/// A property of a buffer.
+///
+/// Properties can be read using [`Buffer::get_property`] and modified using
+/// [`Buffer::set_property`]. The type of the value and whether a property can
+/// be read or modified is checked before Weechat is asked for it.
+///
+/// A detailed description of the properties can be found in the Weechat
+/// [plugin API docs].
+///
+/// Local variables are handled by [`Buffer::get_localvar`] and
+/// [`Buffer::set_localvar`].
+///
+/// [`Buffer::get_property`]: crate::buffer::Buffer::get_property
+/// [`Buffer::set_property`]: crate::buffer::Buffer::set_property
+/// [`Buffer::get_localvar`]: crate::buffer::Buffer::get_localvar
+/// [`Buffer::set_localvar`]: crate::buffer::Buffer::set_localvar
+/// [plugin API docs]: https://weechat.org/files/doc/stable/weechat_plugin_api.en.html#_buffer_get_integer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BufferProperty {
+    /// The number of the buffer, starts at 1. Integer, read-write.
+    Number,
+    /// The number of the buffer in the layout. Integer, read-only.
+    LayoutNumber,
+    /// The order of the buffer in the layout for merged buffers. Integer,
+    /// read-only.
+    LayoutNumberMergeOrder,
+    /// Is the short name of the buffer set. Integer, read-only.
+    ShortNameIsSet,
+    /// The type of the buffer, 0 for formatted and 1 for free content.
+    /// Integer, read-write.
+    Type,
+    /// The notify level of the buffer. Integer, read-write.
+    Notify,
+    /// The number of windows displaying the buffer. Integer, read-only.
+    NumDisplayed,
+    /// Is the buffer active, 2 if the buffer is the only active one of merged
+    /// buffers, 1 if active and 0 if not active. Integer, read-only.
+    Active,
+    /// Is the buffer hidden. Integer, read-write.
+    Hidden,
+    /// Is the buffer zoomed. Integer, read-only.
+    Zoomed,
+    /// Are print hooks enabled for the buffer. Integer, read-write.
+    PrintHooksEnabled,
+    /// Are messages for the day change displayed. Integer, read-write.
+    DayChange,
+    /// Can the buffer be cleared with the `/buffer clear` command. Integer,
+    /// read-write.
+    Clear,
+    /// Are filters enabled on the buffer. Integer, read-write.
+    Filter,
+    /// Is the buffer being closed. Integer, read-only.
+    Closing,
+    /// Is at least one line of the buffer hidden by a filter. Integer,
+    /// read-only.
+    LinesHidden,
+    /// The maximum length of a prefix in the buffer. Integer, read-only.
+    PrefixMaxLength,
+    /// Is the time displayed for each line. Integer, read-write.
+    TimeForEachLine,
+    /// Is the nicklist enabled. Integer, read-write.
+    Nicklist,
+    /// Are nicks case sensitive. Integer, read-write.
+    NicklistCaseSensitive,
+    /// The maximum length of a nick. Integer, read-only.
+    NicklistMaxLength,
+    /// Are groups displayed in the nicklist. Integer, read-write.
+    NicklistDisplayGroups,
+    /// The number of nicks and groups in the nicklist. Integer, read-only.
+    NicklistCount,
+    /// The number of visible nicks and groups in the nicklist. Integer,
+    /// read-only.
+    NicklistVisibleCount,
+    /// The number of groups in the nicklist. Integer, read-only.
+    NicklistGroupsCount,
+    /// The number of visible groups in the nicklist. Integer, read-only.
+    NicklistGroupsVisibleCount,
+    /// The number of nicks in the nicklist. Integer, read-only.
+    NicklistNicksCount,
+    /// The number of visible nicks in the nicklist. Integer, read-only.
+    NicklistNicksVisibleCount,
+    /// Is the input of the buffer enabled. Integer, read-only.
+    InputEnabled,
+    /// Are unknown commands sent to the input callback. Integer, read-write.
+    InputGetUnknownCommands,
+    /// Is empty input sent to the input callback. Integer, read-write.
+    InputGetEmpty,
+    /// Is multiline input enabled. Integer, read-write.
+    InputMultiline,
+    /// The size of the input in bytes. Integer, read-only.
+    InputSize,
+    /// The length of the input in chars. Integer, read-only.
+    InputLength,
+    /// The position of the cursor in the input. Integer, read-write.
+    InputPosition,
+    /// The first char displayed on the screen. Integer, read-only.
+    InputFirstDisplay,
+    /// The number of commands in the history of the buffer. Integer,
+    /// read-only.
+    NumHistory,
+    /// The type of the text search that is in progress. Integer, read-only.
+    TextSearch,
+    /// The direction of the text search. Integer, read-only.
+    TextSearchDirection,
+    /// Is the text search case sensitive. Integer, read-only.
+    TextSearchExact,
+    /// Is the text search done using a regular expression. Integer,
+    /// read-only.
+    TextSearchRegex,
+    /// Where the text search is done, in the prefix, the message or both.
+    /// Integer, read-only.
+    TextSearchWhere,
+    /// Is the text search done in the local or the global history. Integer,
+    /// read-only.
+    TextSearchHistory,
+    /// Was text found by the text search. Integer, read-only.
+    TextSearchFound,
+    /// The name of the plugin that created the buffer. String, read-only.
+    Plugin,
+    /// The name of the buffer. String, read-write.
+    Name,
+    /// The full name of the buffer. String, read-only.
+    FullName,
+    /// The full name of the buffer before it was renamed. String, read-only.
+    OldFullName,
+    /// The short name of the buffer. String, read-write.
+    ShortName,
+    /// The title of the buffer. String, read-write.
+    Title,
+    /// The modes of the buffer. String, read-write.
+    Modes,
+    /// The prompt displayed in the input bar. String, read-write.
+    InputPrompt,
+    /// The content of the input. String, read-write.
+    Input,
+    /// The text that is searched for in the buffer. String, read-only.
+    TextSearchInput,
+    /// A comma separated list of words that highlight. String, read-write.
+    HighlightWords,
+    /// Add words to the highlight words. String, write-only.
+    HighlightWordsAdd,
+    /// Remove words from the highlight words. String, write-only.
+    HighlightWordsDel,
+    /// A regular expression for messages that must not highlight. String,
+    /// read-write.
+    HighlightDisableRegex,
+    /// A regular expression for messages that highlight. String, read-write.
+    HighlightRegex,
+    /// A list of tags lines need to have to highlight. String, read-write.
+    HighlightTagsRestrict,
+    /// A list of tags that force a highlight. String, read-write.
+    HighlightTags,
+    /// The maximum hotlist level for some nicks. String, read-write.
+    HotlistMaxLevelNicks,
+    /// Add nicks to the maximum hotlist level for nicks. String, write-only.
+    HotlistMaxLevelNicksAdd,
+    /// Remove nicks from the maximum hotlist level for nicks. String,
+    /// write-only.
+    HotlistMaxLevelNicksDel,
+    /// Add the buffer to or remove it from the hotlist. String, write-only.
+    Hotlist,
+}
+
+impl BufferProperty {
+    fn info(&self) -> (&'static str, PropertyType, Access) {
+        use Access::*;
+        use BufferProperty::*;
+        use PropertyType::{Integer, String};
+
+        match self {
+            Number => ("number", Integer, ReadWrite),
+            LayoutNumber => ("layout_number", Integer, Read),
+            LayoutNumberMergeOrder => ("layout_number_merge_order", Integer, Read),
+            ShortNameIsSet => ("short_name_is_set", Integer, Read),
+            Type => ("type", Integer, ReadWrite),
+            Notify => ("notify", Integer, ReadWrite),
+            NumDisplayed => ("num_displayed", Integer, Read),
+            Active => ("active", Integer, Read),
+            Hidden => ("hidden", Integer, ReadWrite),
+            Zoomed => ("zoomed", Integer, Read),
+            PrintHooksEnabled => ("print_hooks_enabled", Integer, ReadWrite),
+            DayChange => ("day_change", Integer, ReadWrite),
+            Clear => ("clear", Integer, ReadWrite),
+            Filter => ("filter", Integer, ReadWrite),
+            Closing => ("closing", Integer, Read),
+            LinesHidden => ("lines_hidden", Integer, Read),
+            PrefixMaxLength => ("prefix_max_length", Integer, Read),
+            TimeForEachLine => ("time_for_each_line", Integer, ReadWrite),
+            Nicklist => ("nicklist", Integer, ReadWrite),
+            NicklistCaseSensitive => ("nicklist_case_sensitive", Integer, ReadWrite),
+            NicklistMaxLength => ("nicklist_max_length", Integer, Read),
+            NicklistDisplayGroups => ("nicklist_display_groups", Integer, ReadWrite),
+            NicklistCount => ("nicklist_count", Integer, Read),
+            NicklistVisibleCount => ("nicklist_visible_count", Integer, Read),
+            NicklistGroupsCount => ("nicklist_groups_count", Integer, Read),
+            NicklistGroupsVisibleCount => ("nicklist_groups_visible_count", Integer, Read),
+            NicklistNicksCount => ("nicklist_nicks_count", Integer, Read),
+            NicklistNicksVisibleCount => ("nicklist_nicks_visible_count", Integer, Read),
+            InputEnabled => ("input", Integer, Read),
+            InputGetUnknownCommands => ("input_get_unknown_commands", Integer, ReadWrite),
+            InputGetEmpty => ("input_get_empty", Integer, ReadWrite),
+            InputMultiline => ("input_multiline", Integer, ReadWrite),
+            InputSize => ("input_size", Integer, Read),
+            InputLength => ("input_length", Integer, Read),
+            InputPosition => ("input_pos", Integer, ReadWrite),
+            InputFirstDisplay => ("input_1st_display", Integer, Read),
+            NumHistory => ("num_history", Integer, Read),
+            TextSearch => ("text_search", Integer, Read),
+            TextSearchDirection => ("text_search_direction", Integer, Read),
+            TextSearchExact => ("text_search_exact", Integer, Read),
+            TextSearchRegex => ("text_search_regex", Integer, Read),
+            TextSearchWhere => ("text_search_where", Integer, Read),
+            TextSearchHistory => ("text_search_history", Integer, Read),
+            TextSearchFound => ("text_search_found", Integer, Read),
+            Plugin => ("plugin", String, Read),
+            Name => ("name", String, ReadWrite),
+            FullName => ("full_name", String, Read),
+            OldFullName => ("old_full_name", String, Read),
+            ShortName => ("short_name", String, ReadWrite),
+            Title => ("title", String, ReadWrite),
+            Modes => ("modes", String, ReadWrite),
+            InputPrompt => ("input_prompt", String, ReadWrite),
+            Input => ("input", String, ReadWrite),
+            TextSearchInput => ("text_search_input", String, Read),
+            HighlightWords => ("highlight_words", String, ReadWrite),
+            HighlightWordsAdd => ("highlight_words_add", String, Write),
+            HighlightWordsDel => ("highlight_words_del", String, Write),
+            HighlightDisableRegex => ("highlight_disable_regex", String, ReadWrite),
+            HighlightRegex => ("highlight_regex", String, ReadWrite),
+            HighlightTagsRestrict => ("highlight_tags_restrict", String, ReadWrite),
+            HighlightTags => ("highlight_tags", String, ReadWrite),
+            HotlistMaxLevelNicks => ("hotlist_max_level_nicks", String, ReadWrite),
+            HotlistMaxLevelNicksAdd => ("hotlist_max_level_nicks_add", String, Write),
+            HotlistMaxLevelNicksDel => ("hotlist_max_level_nicks_del", String, Write),
+            Hotlist => ("hotlist", String, Write),
+        }
+    }
+
+    /// The name Weechat uses for the property.
+    pub fn name(&self) -> &'static str {
+        self.info().0
+    }
+
+    pub(crate) fn property_type(&self) -> PropertyType {
+        self.info().1
+    }
+
+    /// Can the value of the property be read.
+    pub fn is_readable(&self) -> bool {
+        matches!(self.info().2, Access::Read | Access::ReadWrite)
+    }
+
+    /// Can the value of the property be modified.
+    pub fn is_writable(&self) -> bool {
+        matches!(self.info().2, Access::Write | Access::ReadWrite)
+    }
+}
+
+/// A pointer property of a buffer.
+///
+/// Pointer properties can be read using [`Buffer::get_pointer_property`], they
+/// can't be modified.
+///
+/// [`Buffer::get_pointer_property`]: crate::buffer::Buffer::get_pointer_property
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BufferPointerProperty {
+    /// The plugin that created the buffer, null for Weechat core buffers.
+    Plugin,
+    /// The compiled regular expression of the text search.
+    TextSearchRegexCompiled,
+    /// The history entry the text search in the input history is currently
+    /// on.
+    TextSearchHistoryEntry,
+    /// The compiled regular expression for messages that must not highlight.
+    HighlightDisableRegexCompiled,
+    /// The compiled regular expression for messages that highlight.
+    HighlightRegexCompiled,
+}
+
+impl BufferPointerProperty {
+    /// The name Weechat uses for the property.
+    pub fn name(&self) -> &'static str {
+        use BufferPointerProperty::*;
+
+        match self {
+            Plugin => "plugin",
+            TextSearchRegexCompiled => "text_search_regex_compiled",
+            TextSearchHistoryEntry => "text_search_ptr_history",
+            HighlightDisableRegexCompiled => "highlight_disable_regex_compiled",
+            HighlightRegexCompiled => "highlight_regex_compiled",
+        }
+    }
+}
@@ -0,0 +1,508 @@
+#[cfg(feature = "async")]
+use std::ptr;
+use std::{
+    ffi::c_void,
+    marker::PhantomData,
+    time::{Duration, SystemTime},
+};
+
+use libc::c_int;
+#[cfg(feature = "async")]
+use weechat_sys::t_hdata;
+
+#[cfg(feature = "async")]
+use crate::{buffer::BufferHandle, executor::yield_now};
+use crate::{
+    buffer::{Buffer, BufferLine, BufferLines},
+    regex::Regex,
+    LossyCString, Weechat,
+};
+
+/// The direction in which the lines of a buffer are searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchDirection {
+    /// Search from the oldest line of the buffer to the newest one.
+    Forward,
+    /// Search from the newest line of the buffer to the oldest one.
+    Backward,
+}
+
+/// The part of a line the text pattern of a search is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    /// Match the pattern against the message of the line.
+    Message,
+    /// Match the pattern against the prefix of the line.
+    Prefix,
+    /// Match the pattern against the prefix or the message of the line.
+    PrefixAndMessage,
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    Regex(String),
+    Mask { mask: String, case_sensitive: bool },
+}
+
+/// A query describing the lines of a buffer that should be found.
+///
+/// A line needs to match all the conditions of the query to be found, a
+/// query without any conditions finds all lines.
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::buffer::{BufferBuilder, LineQuery, SearchDirection};
+/// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+/// # let buffer = buffer_handle.upgrade().unwrap();
+/// let query = LineQuery::new()
+///     .regex("(?i)weechat")
+///     .tag("irc_privmsg")
+///     .exclude_tag("self_msg")
+///     .direction(SearchDirection::Backward)
+///     .limit(10);
+///
+/// for line in buffer.search_lines(&query).unwrap() {
+///     println!("{}", line.message());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineQuery {
+    pattern: Option<Pattern>,
+    field: SearchField,
+    required_tags: Vec<String>,
+    excluded_tags: Vec<String>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    highlight_only: bool,
+    displayed_only: bool,
+    direction: SearchDirection,
+    limit: Option<usize>,
+}
+
+impl Default for LineQuery {
+    fn default() -> Self {
+        LineQuery::new()
+    }
+}
+
+impl LineQuery {
+    /// Create a new query that finds all lines, searching forward.
+    pub fn new() -> Self {
+        LineQuery {
+            pattern: None,
+            field: SearchField::Message,
+            required_tags: Vec::new(),
+            excluded_tags: Vec::new(),
+            since: None,
+            until: None,
+            highlight_only: false,
+            displayed_only: false,
+            direction: SearchDirection::Forward,
+            limit: None,
+        }
+    }
+
+    /// Only find lines that match the given POSIX extended regular
+    /// expression.
+    ///
+    /// The regular expression is matched against the text without colors,
+    /// `(?i)` at the start of the expression makes it case insensitive.
+    /// Replaces a previously set mask.
+    pub fn regex(mut self, regex: &str) -> Self {
+        self.pattern = Some(Pattern::Regex(regex.to_owned()));
+        self
+    }
+
+    /// Only find lines that match the given Weechat mask.
+    ///
+    /// The mask is matched against the text without colors, a `*` in the mask
+    /// matches any number of chars. Replaces a previously set regular
+    /// expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The mask, for example `*hello*`.
+    ///
+    /// * `case_sensitive` - Should the case of the text matter.
+    pub fn mask(mut self, mask: &str, case_sensitive: bool) -> Self {
+        self.pattern = Some(Pattern::Mask { mask: mask.to_owned(), case_sensitive });
+        self
+    }
+
+    /// Set the part of the line the regular expression or mask is matched
+    /// against, by default the message is used.
+    pub fn field(mut self, field: SearchField) -> Self {
+        self.field = field;
+        self
+    }
+
+    /// Only find lines that have the given tag.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.required_tags.push(tag.to_owned());
+        self
+    }
+
+    /// Only find lines that don't have the given tag.
+    pub fn exclude_tag(mut self, tag: &str) -> Self {
+        self.excluded_tags.push(tag.to_owned());
+        self
+    }
+
+    /// Only find lines with a date at or after the given time.
+    ///
+    /// The date of a line is compared with a precision of seconds.
+    pub fn since(mut self, time: SystemTime) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Only find lines with a date at or before the given time.
+    ///
+    /// The date of a line is compared with a precision of seconds.
+    pub fn until(mut self, time: SystemTime) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Only find highlighted lines.
+    pub fn highlight_only(mut self, highlight_only: bool) -> Self {
+        self.highlight_only = highlight_only;
+        self
+    }
+
+    /// Only find lines that are displayed, lines hidden by a filter are
+    /// skipped.
+    pub fn displayed_only(mut self, displayed_only: bool) -> Self {
+        self.displayed_only = displayed_only;
+        self
+    }
+
+    /// Set the direction in which the buffer is searched.
+    pub fn direction(mut self, direction: SearchDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Stop the search after the given number of lines were found.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn limit_reached(&self, found: usize) -> bool {
+        self.limit.is_some_and(|limit| found >= limit)
+    }
+}
+
+/// A query that is ready to be matched against lines.
+struct LineMatcher {
+    weechat: Weechat,
+    query: LineQuery,
+    regex: Option<Regex>,
+}
+
+impl LineMatcher {
+    fn new(weechat: Weechat, query: LineQuery) -> Result<Self, ()> {
+        let regex = match &query.pattern {
+            Some(Pattern::Regex(regex)) => Some(Regex::new(&weechat, regex)?),
+            _ => None,
+        };
+
+        Ok(LineMatcher { weechat, query, regex })
+    }
+
+    fn text_matches(&self, text: &str) -> bool {
+        let text = Weechat::remove_color(text);
+
+        match (&self.query.pattern, &self.regex) {
+            (None, _) => true,
+            (Some(Pattern::Regex(_)), regex) => regex.as_ref().is_some_and(|r| r.is_match(&text)),
+            (Some(Pattern::Mask { mask, case_sensitive }), _) => {
+                let string_match = self.weechat.get().string_match.unwrap();
+                let text = LossyCString::new(text);
+                let mask = LossyCString::new(mask);
+
+                unsafe { string_match(text.as_ptr(), mask.as_ptr(), *case_sensitive as c_int) == 1 }
+            }
+        }
+    }
+
+    fn matches(&self, line: &BufferLine) -> bool {
+        let query = &self.query;
+
+        if query.highlight_only && !line.highlighted() {
+            return false;
+        }
+
+        if query.displayed_only && !line.displayed() {
+            return false;
+        }
+
+        if query.since.is_some() || query.until.is_some() {
+            let date = SystemTime::UNIX_EPOCH + Duration::from_secs(line.date().max(0) as u64);
+
+            if query.since.is_some_and(|since| date < since)
+                || query.until.is_some_and(|until| date > until)
+            {
+                return false;
+            }
+        }
+
+        if !query.required_tags.is_empty() || !query.excluded_tags.is_empty() {
+            let tags = line.tags();
+            let has_tag = |tag: &String| tags.iter().any(|t| t == tag);
+
+            if !query.required_tags.iter().all(has_tag) || query.excluded_tags.iter().any(has_tag) {
+                return false;
+            }
+        }
+
+        if query.pattern.is_none() {
+            return true;
+        }
+
+        match query.field {
+            SearchField::Message => self.text_matches(&line.message()),
+            SearchField::Prefix => self.text_matches(&line.prefix()),
+            SearchField::PrefixAndMessage => {
+                self.text_matches(&line.prefix()) || self.text_matches(&line.message())
+            }
+        }
+    }
+}
+
+fn next_line<'a>(
+    lines: &mut BufferLines<'a>,
+    direction: SearchDirection,
+) -> Option<BufferLine<'a>> {
+    match direction {
+        SearchDirection::Forward => lines.next(),
+        SearchDirection::Backward => lines.next_back(),
+    }
+}
+
+/// An iterator over the lines of a buffer that match a [`LineQuery`].
+pub struct LineSearch<'a> {
+    lines: BufferLines<'a>,
+    matcher: LineMatcher,
+    found: usize,
+}
+
+impl<'a> Iterator for LineSearch<'a> {
+    type Item = BufferLine<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let query = &self.matcher.query;
+
+        if query.limit_reached(self.found) {
+            return None;
+        }
+
+        while let Some(line) = next_line(&mut self.lines, query.direction) {
+            if self.matcher.matches(&line) {
+                self.found += 1;
+                return Some(line);
+            }
+        }
+
+        None
+    }
+}
+
+impl Buffer<'_> {
+    fn lines_between(&self, first_line: *mut c_void, last_line: *mut c_void) -> BufferLines<'_> {
+        BufferLines {
+            weechat_ptr: self.weechat().ptr,
+            first_line,
+            last_line,
+            buffer: PhantomData,
+            done: false,
+        }
+    }
+
+    /// Search the lines of the buffer.
+    ///
+    /// Returns an iterator over the lines that match the query, in the
+    /// direction the query specifies. Returns an error if the regular
+    /// expression of the query isn't valid.
+    ///
+    /// The whole buffer is searched synchronously, for buffers with a lot of
+    /// lines consider using [`BufferHandle::search_lines_chunked`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query the lines need to match.
+    pub fn search_lines(&self, query: &LineQuery) -> Result<LineSearch<'_>, ()> {
+        let matcher = LineMatcher::new(Weechat::from_ptr(self.weechat().ptr), query.clone())?;
+
+        Ok(LineSearch { lines: self.lines(), matcher, found: 0 })
+    }
+}
+
+/// A cheap snapshot of the line list of a buffer, used to notice changes of
+/// the list while a chunked search is yielding.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LinesSnapshot {
+    first_line: *mut c_void,
+    last_line: *mut c_void,
+    count: i32,
+}
+
+#[cfg(feature = "async")]
+impl LinesSnapshot {
+    /// Were lines only appended to the list since this snapshot was taken.
+    ///
+    /// The check walks back over the appended lines only, if they lead to the
+    /// previous last line and the line count grew by their number no line
+    /// was removed.
+    fn only_appended(&self, weechat: &Weechat, line_hdata: *mut t_hdata, now: &Self) -> bool {
+        if now.first_line != self.first_line || now.count < self.count {
+            return false;
+        }
+
+        let mut line = now.last_line;
+
+        for _ in 0..now.count - self.count {
+            if line.is_null() {
+                return false;
+            }
+
+            line = unsafe { weechat.hdata_move(line_hdata, line, -1) };
+        }
+
+        line == self.last_line
+    }
+}
+
+#[cfg(feature = "async")]
+impl Buffer<'_> {
+    fn lines_snapshot(&self) -> LinesSnapshot {
+        let weechat = self.weechat();
+
+        let count = unsafe {
+            let lines = weechat.hdata_get("lines");
+            weechat.hdata_integer(lines, self.own_lines(), "lines_count")
+        };
+
+        LinesSnapshot {
+            first_line: self.first_line_pointer(),
+            last_line: self.last_line_pointer(),
+            count,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl BufferHandle {
+    /// Search the lines of the buffer in chunks, yielding to the executor
+    /// after every chunk.
+    ///
+    /// Weechat gets to handle other events once the time budget of the
+    /// executor is used up, see [`Weechat::set_executor_time_budget`].
+    ///
+    /// The callback is called for every line that matches the query. Returns
+    /// the number of lines that were found.
+    ///
+    /// Returns an error if the regular expression of the query isn't valid, if
+    /// the buffer was closed or if the line the search would continue from
+    /// was removed while the search was yielding.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query the lines need to match.
+    ///
+    /// * `chunk_size` - The number of lines that are searched before yielding
+    ///   to the executor.
+    ///
+    /// * `callback` - The callback that is called for every found line.
+    pub async fn search_lines_chunked(
+        &self,
+        query: &LineQuery,
+        chunk_size: usize,
+        mut callback: impl FnMut(&BufferLine),
+    ) -> Result<usize, ()> {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let matcher = LineMatcher::new(Weechat::from_ptr(self.weechat), query.clone())?;
+        let direction = query.direction;
+        let chunk_size = chunk_size.max(1);
+        let mut found = 0;
+
+        let (mut next, mut snapshot) = {
+            let buffer = self.upgrade()?;
+
+            let next = match direction {
+                SearchDirection::Forward => buffer.first_line_pointer(),
+                SearchDirection::Backward => buffer.last_line_pointer(),
+            };
+
+            (next, buffer.lines_snapshot())
+        };
+
+        while !next.is_null() && !query.limit_reached(found) {
+            next = {
+                let buffer = self.upgrade()?;
+                let line_hdata = unsafe { weechat.hdata_get("line") };
+
+                // Checking the pointer walks the whole line list, only do so
+                // if lines might have been removed while the search was
+                // yielding.
+                if !snapshot.only_appended(&weechat, line_hdata, &buffer.lines_snapshot()) {
+                    let valid = unsafe {
+                        weechat.hdata_check_pointer(line_hdata, buffer.first_line_pointer(), next)
+                    };
+
+                    if !valid {
+                        return Err(());
+                    }
+                }
+
+                let mut lines = match direction {
+                    SearchDirection::Forward => {
+                        buffer.lines_between(next, buffer.last_line_pointer())
+                    }
+                    SearchDirection::Backward => {
+                        buffer.lines_between(buffer.first_line_pointer(), next)
+                    }
+                };
+
+                let mut last: *mut c_void = ptr::null_mut();
+
+                for _ in 0..chunk_size {
+                    let Some(line) = next_line(&mut lines, direction) else {
+                        break;
+                    };
+
+                    last = line.line_pointer();
+
+                    if matcher.matches(&line) {
+                        callback(&line);
+                        found += 1;
+
+                        if query.limit_reached(found) {
+                            break;
+                        }
+                    }
+                }
+
+                snapshot = buffer.lines_snapshot();
+
+                if last.is_null() {
+                    ptr::null_mut()
+                } else {
+                    let step = match direction {
+                        SearchDirection::Forward => 1,
+                        SearchDirection::Backward => -1,
+                    };
+
+                    unsafe { weechat.hdata_move(line_hdata, last, step) }
+                }
+            };
+
+            yield_now().await;
+        }
+
+        Ok(found)
+    }
+}
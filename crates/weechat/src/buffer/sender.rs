@@ -0,0 +1,105 @@
+use crate::{
+    buffer::{BufferHandle, BufferProperty, BufferPropertyValue, HotlistPriority, LineBuilder},
+    executor::WeechatExecutor,
+    Weechat,
+};
+
+enum BufferMessage {
+    Print(LineBuilder),
+    Title(String),
+    Hotlist(HotlistPriority),
+    Property(BufferProperty, BufferPropertyValue<'static>),
+}
+
+/// A handle that can be used to update a buffer from any thread.
+///
+/// The updates are queued and applied on the main Weechat thread in the order
+/// they were sent. Updates for a buffer that was closed in the meantime are
+/// dropped, as are updates that are sent while the plugin is being unloaded.
+///
+/// The buffer is found again using its full name, updates are dropped if the
+/// buffer was renamed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::buffer::BufferBuilder;
+/// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+/// let sender = buffer_handle.sender();
+///
+/// std::thread::spawn(move || {
+///     sender.print("Hello from a worker thread");
+///     sender.set_title("Done");
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct BufferSender {
+    buffer_name: String,
+}
+
+impl BufferSender {
+    fn send(&self, message: BufferMessage) {
+        let buffer_name = self.buffer_name.clone();
+
+        let _ = WeechatExecutor::try_spawn_from_non_main(async move {
+            let weechat = unsafe { Weechat::weechat() };
+
+            let buffer = match weechat.buffer_search("==", &buffer_name) {
+                Some(buffer) => buffer,
+                None => return,
+            };
+
+            match message {
+                BufferMessage::Print(line) => line.print(&buffer),
+                BufferMessage::Title(title) => buffer.set_title(&title),
+                BufferMessage::Hotlist(priority) => buffer.set_hotlist(priority),
+                BufferMessage::Property(property, value) => {
+                    let _ = buffer.set_property(property, value);
+                }
+            }
+        });
+    }
+
+    /// The full name of the buffer the updates are sent to.
+    pub fn buffer_name(&self) -> &str {
+        &self.buffer_name
+    }
+
+    /// Print a message on the buffer.
+    pub fn print(&self, message: &str) {
+        self.send(BufferMessage::Print(LineBuilder::new(message)))
+    }
+
+    /// Print a line on the buffer.
+    pub fn print_line(&self, line: LineBuilder) {
+        self.send(BufferMessage::Print(line))
+    }
+
+    /// Set the title of the buffer.
+    pub fn set_title(&self, title: &str) {
+        self.send(BufferMessage::Title(title.to_owned()))
+    }
+
+    /// Add the buffer to the hotlist with the given priority.
+    pub fn set_hotlist(&self, priority: HotlistPriority) {
+        self.send(BufferMessage::Hotlist(priority))
+    }
+
+    /// Modify a property of the buffer.
+    ///
+    /// Errors while setting the property are ignored.
+    pub fn set_property(
+        &self,
+        property: BufferProperty,
+        value: impl Into<BufferPropertyValue<'static>>,
+    ) {
+        self.send(BufferMessage::Property(property, value.into()))
+    }
+}
+
+impl BufferHandle {
+    /// Get a sender that can be used to update the buffer from other threads.
+    pub fn sender(&self) -> BufferSender {
+        BufferSender { buffer_name: (*self.buffer_name).clone() }
+    }
+}
@@ -1,10 +1,126 @@
 use std::{marker::PhantomData, ptr};
 
-use weechat_sys::{t_gui_window, t_weechat_plugin};
+use weechat_sys::{t_gui_window, t_weechat_plugin, WEECHAT_RC_OK};
 
 use super::Buffer;
 use crate::{LossyCString, Weechat};
 
+/// Which part of a layout should be saved or applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutScope {
+    /// Both the buffer numbers and the window arrangement.
+    All,
+    /// Only the buffer numbers.
+    Buffers,
+    /// Only the window arrangement.
+    Windows,
+}
+
+impl LayoutScope {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LayoutScope::All => "",
+            LayoutScope::Buffers => " buffers",
+            LayoutScope::Windows => " windows",
+        }
+    }
+}
+
+impl Weechat {
+    /// Get the window that is currently displayed.
+    pub fn current_window(&self) -> Window<'_> {
+        let get_pointer = self.get().window_get_pointer.unwrap();
+        let property = LossyCString::new("current");
+
+        let ptr = unsafe { get_pointer(ptr::null_mut(), property.as_ptr()) };
+
+        Window { weechat: self.ptr, ptr: ptr as _, phantom: PhantomData }
+    }
+
+    /// Search the window that is displaying the given buffer.
+    ///
+    /// Returns `None` if no window is displaying the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer for which the window should be found.
+    pub fn window_search_with_buffer(&self, buffer: &Buffer) -> Option<Window<'_>> {
+        let search = self.get().window_search_with_buffer.unwrap();
+
+        let ptr = unsafe { search(buffer.ptr()) };
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Window { weechat: self.ptr, ptr, phantom: PhantomData })
+        }
+    }
+
+    /// Save the current layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the layout, if a layout with the same name
+    ///   exists it will be overwritten.
+    ///
+    /// * `scope` - Which part of the current layout should be saved.
+    ///
+    /// Returns an error if the name is empty, contains whitespace or starts
+    /// with a `-`.
+    pub fn save_layout(&self, name: &str, scope: LayoutScope) -> Result<(), ()> {
+        self.layout_command("store", name, scope.as_str())
+    }
+
+    /// Apply a previously saved layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the layout that should be applied.
+    ///
+    /// * `scope` - Which part of the saved layout should be applied.
+    ///
+    /// Returns an error if the name is empty, contains whitespace or starts
+    /// with a `-`.
+    pub fn apply_layout(&self, name: &str, scope: LayoutScope) -> Result<(), ()> {
+        self.layout_command("apply", name, scope.as_str())
+    }
+
+    /// Delete a saved layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the layout that should be deleted.
+    ///
+    /// Returns an error if the name is empty, contains whitespace or starts
+    /// with a `-`.
+    pub fn remove_layout(&self, name: &str) -> Result<(), ()> {
+        self.layout_command("del", name, "")
+    }
+
+    fn layout_command(&self, action: &str, name: &str, scope: &str) -> Result<(), ()> {
+        // The name has to stay a single argument, a name starting with a `-`
+        // would be parsed as an option of the command.
+        if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+            return Err(());
+        }
+
+        self.run_global_command(&format!("/layout {action} {name}{scope}"))
+    }
+
+    pub(crate) fn run_global_command(&self, command: &str) -> Result<(), ()> {
+        let run_command = self.get().command.unwrap();
+        let command = LossyCString::new(command);
+
+        let ret = unsafe { run_command(self.ptr, ptr::null_mut(), command.as_ptr()) };
+
+        if ret == WEECHAT_RC_OK {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
 /// A Weechat window.
 ///
 /// A window is a screen area which displays a buffer. It is possible to split
@@ -109,6 +225,88 @@ impl Window<'_> {
         self.get_integer("lines_after")
     }
 
+    fn window_command(&self, command: &str) -> Result<(), ()> {
+        let weechat = Weechat::from_ptr(self.weechat);
+        weechat.run_global_command(&format!("/window {command}"))
+    }
+
+    fn window_command_with_args(&self, command: &str, arguments: &str) -> Result<(), ()> {
+        let command = format!("{command} -window {} {arguments}", self.number());
+        self.window_command(command.trim_end())
+    }
+
+    /// Scroll the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The number of lines to scroll, negative numbers scroll up
+    ///   and positive ones down.
+    pub fn scroll(&self, lines: i32) -> Result<(), ()> {
+        self.window_command_with_args("scroll", &format!("{lines:+}"))
+    }
+
+    /// Scroll the window to the bottom of the buffer.
+    pub fn scroll_to_bottom(&self) -> Result<(), ()> {
+        self.window_command_with_args("scroll_bottom", "")
+    }
+
+    /// Display the given buffer in the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer that should be displayed.
+    pub fn set_buffer(&self, buffer: &Buffer) -> Result<(), ()> {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let current = weechat.current_window().number();
+
+        self.window_command(&self.number().to_string())?;
+        buffer.switch_to();
+
+        if current != self.number() {
+            self.window_command(&current.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Split the window horizontally, the new window is placed above this
+    /// one.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentage` - The size of the new window as a percentage of the
+    ///   size of this window.
+    pub fn split_horizontal(&self, percentage: u8) -> Result<(), ()> {
+        self.window_command_with_args("splith", &percentage.to_string())
+    }
+
+    /// Split the window vertically, the new window is placed on the right
+    /// side of this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentage` - The size of the new window as a percentage of the
+    ///   size of this window.
+    pub fn split_vertical(&self, percentage: u8) -> Result<(), ()> {
+        self.window_command_with_args("splitv", &percentage.to_string())
+    }
+
+    /// Resize the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentage` - The new size of the window as a percentage of the
+    ///   parent window.
+    pub fn resize(&self, percentage: u8) -> Result<(), ()> {
+        self.window_command_with_args("resize", &percentage.to_string())
+    }
+
+    /// Toggle the zoom of the window, a zoomed window takes up the whole
+    /// screen.
+    pub fn zoom(&self) -> Result<(), ()> {
+        self.window_command_with_args("zoom", "")
+    }
+
     fn set_title_helper(&self, title: Option<&str>) {
         let weechat = Weechat::from_ptr(self.weechat);
         let set_title = weechat.get().window_set_title.unwrap();
@@ -0,0 +1,177 @@
+use std::{fmt, io};
+
+use crate::buffer::{Buffer, LineBuilder};
+
+/// A writer that prints the text written to it on a buffer.
+///
+/// The text is split into lines, every complete line is printed as soon as
+/// it's written. Text that isn't terminated by a newline is kept until the
+/// rest of the line is written, the writer is flushed or the writer is
+/// dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::fmt::Write;
+/// # use weechat::buffer::{BufferBuilder, BufferWriter};
+/// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+/// # let buffer = buffer_handle.upgrade().unwrap();
+/// let mut writer = BufferWriter::new(&buffer).prefix("--").tag("no_log");
+///
+/// writeln!(writer, "Connected to {}", "example.org").unwrap();
+/// write!(writer, "Joined ").unwrap();
+/// writeln!(writer, "{} rooms", 3).unwrap();
+/// ```
+pub struct BufferWriter<'a> {
+    buffer: &'a Buffer<'a>,
+    prefix: Option<String>,
+    tags: Vec<String>,
+    pending: Vec<u8>,
+}
+
+impl<'a> BufferWriter<'a> {
+    /// Create a new writer for the given buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer the written lines are printed on.
+    pub fn new(buffer: &'a Buffer<'a>) -> Self {
+        BufferWriter { buffer, prefix: None, tags: Vec::new(), pending: Vec::new() }
+    }
+
+    /// Set the prefix that every printed line gets.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_owned());
+        self
+    }
+
+    /// Add a tag that every printed line gets.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_owned());
+        self
+    }
+
+    /// Add multiple tags that every printed line gets.
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.tags.extend(tags.iter().map(|t| (*t).to_owned()));
+        self
+    }
+
+    fn print_line(&self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+
+        let mut builder = LineBuilder::new(line);
+
+        if let Some(prefix) = &self.prefix {
+            builder = builder.prefix(prefix);
+        }
+
+        for tag in &self.tags {
+            builder = builder.tag(tag);
+        }
+
+        builder.print(self.buffer);
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+
+        if let Some(end) = self.pending.iter().rposition(|b| *b == b'\n') {
+            let rest = self.pending.split_off(end + 1);
+            let complete = std::mem::replace(&mut self.pending, rest);
+
+            for line in complete[..end].split(|b| *b == b'\n') {
+                self.print_line(line);
+            }
+        }
+    }
+
+    fn flush_pending(&mut self) {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            self.print_line(&line);
+        }
+    }
+}
+
+impl fmt::Write for BufferWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl io::Write for BufferWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.push(buf);
+        Ok(buf.len())
+    }
+
+    /// Print the text that isn't terminated by a newline as a line of its
+    /// own.
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_pending();
+        Ok(())
+    }
+}
+
+impl Drop for BufferWriter<'_> {
+    fn drop(&mut self) {
+        self.flush_pending();
+    }
+}
+
+impl Buffer<'_> {
+    /// Get a writer that prints the text written to it on this buffer.
+    pub fn writer(&self) -> BufferWriter<'_> {
+        BufferWriter::new(self)
+    }
+}
+
+/// Print formatted text on a buffer.
+///
+/// The text is split into lines, every line is printed separately.
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::{buffer::BufferBuilder, wprint};
+/// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+/// # let buffer = buffer_handle.upgrade().unwrap();
+/// wprint!(buffer, "{} users online", 3);
+/// ```
+#[macro_export]
+macro_rules! wprint {
+    ($buffer:expr, $($arg:tt)*) => {{
+        let mut writer = $crate::buffer::BufferWriter::new(&$buffer);
+        let _ = ::std::fmt::Write::write_fmt(&mut writer, ::std::format_args!($($arg)*));
+    }};
+}
+
+/// Print formatted text on a buffer, followed by a newline.
+///
+/// The text is split into lines, every line is printed separately. Without a
+/// format string an empty line is printed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::{buffer::BufferBuilder, wprintln};
+/// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+/// # let buffer = buffer_handle.upgrade().unwrap();
+/// wprintln!(buffer, "Hello {}", "world");
+/// wprintln!(buffer);
+/// ```
+#[macro_export]
+macro_rules! wprintln {
+    ($buffer:expr) => {{
+        let mut writer = $crate::buffer::BufferWriter::new(&$buffer);
+        let _ = ::std::fmt::Write::write_str(&mut writer, "\n");
+    }};
+    ($buffer:expr, $($arg:tt)*) => {{
+        let mut writer = $crate::buffer::BufferWriter::new(&$buffer);
+        let _ = ::std::fmt::Write::write_fmt(&mut writer, ::std::format_args!($($arg)*));
+        let _ = ::std::fmt::Write::write_str(&mut writer, "\n");
+    }};
+}
@@ -14,7 +14,7 @@ use weechat_sys::{
     t_config_file, t_config_option, t_config_section, t_weechat_plugin, WEECHAT_RC_OK,
 };
 
-#[cfg(not(weechat410))]
+#[cfg(weechat420)]
 use crate::config::EnumOption;
 use crate::{
     config::{
@@ -317,7 +317,7 @@ impl Config {
                 weechat_ptr,
                 _phantom: PhantomData,
             }),
-            #[cfg(not(weechat410))]
+            #[cfg(weechat420)]
             "enum" => ConfigOption::Enum(EnumOption {
                 ptr: option_ptr,
                 weechat_ptr,
@@ -25,7 +25,7 @@ pub struct IntegerOptionSettings {
 
     pub(crate) max: i32,
 
-    #[cfg(weechat410)]
+    #[cfg(not(weechat420))]
     pub(crate) string_values: String,
 
     pub(crate) change_cb: Option<IntegerOptionCallback>,
@@ -80,7 +80,7 @@ impl IntegerOptionSettings {
     /// let settings = IntegerOptionSettings::new("server_buffer")
     ///     .string_values(vec!["independent", "merged"]);
     /// ```
-    #[cfg(weechat410)]
+    #[cfg(not(weechat420))]
     pub fn string_values<I, T>(mut self, values: I) -> Self
     where
         I: IntoIterator<Item = T>,
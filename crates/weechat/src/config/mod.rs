@@ -28,13 +28,13 @@ mod color;
 #[allow(clippy::module_inception)]
 mod config;
 mod config_options;
-#[cfg(not(weechat410))]
+#[cfg(weechat420)]
 mod r#enum;
 mod integer;
 mod section;
 mod string;
 
-#[cfg(not(weechat410))]
+#[cfg(weechat420)]
 pub use crate::config::r#enum::{EnumOption, EnumOptionSettings};
 pub use crate::config::{
     boolean::{BooleanOption, BooleanOptionSettings},
@@ -12,7 +12,7 @@ use std::{
 use weechat_sys::{t_config_file, t_config_option, t_config_section, t_weechat_plugin};
 
 use super::config_options::OptionCallback;
-#[cfg(not(weechat410))]
+#[cfg(weechat420)]
 use crate::config::{EnumOption, EnumOptionSettings};
 use crate::{
     config::{
@@ -43,7 +43,7 @@ pub enum ConfigOption<'a> {
     Integer(IntegerOption<'a>),
     String(StringOption<'a>),
     Color(ColorOption<'a>),
-    #[cfg(not(weechat410))]
+    #[cfg(weechat420)]
     Enum(EnumOption<'a>),
 }
 
@@ -54,7 +54,7 @@ impl<'a> ConfigOption<'a> {
             ConfigOption::Boolean(ref o) => o,
             ConfigOption::Integer(ref o) => o,
             ConfigOption::String(ref o) => o,
-            #[cfg(not(weechat410))]
+            #[cfg(weechat420)]
             ConfigOption::Enum(ref o) => o,
         }
     }
@@ -97,7 +97,7 @@ impl<'a> AsRef<dyn BaseConfigOption + 'a> for StringOption<'a> {
     }
 }
 
-#[cfg(not(weechat410))]
+#[cfg(weechat420)]
 impl<'a> AsRef<dyn BaseConfigOption + 'a> for EnumOption<'a> {
     fn as_ref(&self) -> &(dyn BaseConfigOption + 'a) {
         self
@@ -116,7 +116,7 @@ pub(crate) enum ConfigOptionPointers {
     Integer(*const c_void),
     String(*const c_void),
     Color(*const c_void),
-    #[cfg(not(weechat410))]
+    #[cfg(weechat420)]
     Enum(*const c_void),
 }
 
@@ -391,7 +391,7 @@ impl Drop for ConfigSection {
                     ConfigOptionPointers::Color(p) => {
                         drop(Box::from_raw(p as *mut OptionPointers<ColorOption>));
                     }
-                    #[cfg(not(weechat410))]
+                    #[cfg(weechat420)]
                     ConfigOptionPointers::Enum(p) => {
                         drop(Box::from_raw(p as *mut OptionPointers<EnumOption>));
                     }
@@ -595,7 +595,7 @@ impl ConfigSection {
                 description: &settings.description,
                 min: settings.min,
                 max: settings.max,
-                #[cfg(weechat410)]
+                #[cfg(not(weechat420))]
                 string_values: &settings.string_values,
                 default_value: &settings.default_value.to_string(),
                 value: &settings.default_value.to_string(),
@@ -664,7 +664,7 @@ impl ConfigSection {
     ///
     /// # Arguments
     /// * `settings` - Settings that decide how the option should be created.
-    #[cfg(not(weechat410))]
+    #[cfg(weechat420)]
     pub fn new_enum_option(&mut self, settings: EnumOptionSettings) -> Result<EnumOption<'_>, ()> {
         let ret = self.new_option(
             OptionDescription {
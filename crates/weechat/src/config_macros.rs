@@ -20,7 +20,7 @@ macro_rules! option_settings {
     };
 }
 
-#[cfg(not(weechat410))]
+#[cfg(weechat420)]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! option_settings_enum {
@@ -37,7 +37,7 @@ macro_rules! option_settings_enum {
     };
 }
 
-#[cfg(weechat410)]
+#[cfg(not(weechat420))]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! option_settings_enum {
@@ -110,7 +110,7 @@ macro_rules! option_getter {
     };
 }
 
-#[cfg(not(weechat410))]
+#[cfg(weechat420)]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! option {
@@ -145,7 +145,7 @@ macro_rules! option {
     };
 }
 
-#[cfg(weechat410)]
+#[cfg(not(weechat420))]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! option {
@@ -1,7 +1,13 @@
 use std::{
     collections::VecDeque,
     panic,
-    sync::{Arc, Mutex},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 pub use async_task::{Runnable, Task};
@@ -41,12 +47,104 @@ enum ExecutorJob {
 
 type FutureQueue = Arc<Mutex<VecDeque<ExecutorJob>>>;
 
+/// The time the executor may spend running tasks per wakeup by default.
+const DEFAULT_TIME_BUDGET: Duration = Duration::from_millis(10);
+
+#[derive(Default)]
+struct Counters {
+    queued: AtomicU64,
+    run: AtomicU64,
+    cancelled: AtomicU64,
+}
+
+/// Counts the task it's moved into as cancelled if it's dropped before the
+/// task completes.
+struct CancelGuard(Option<Arc<Counters>>);
+
+impl CancelGuard {
+    fn wrap<F: Future>(counters: Arc<Counters>, future: F) -> impl Future<Output = F::Output> {
+        // The guard is created outside of the async block, so it's dropped
+        // even if the task is dropped before it's polled for the first time.
+        let mut guard = CancelGuard(Some(counters));
+
+        async move {
+            let output = future.await;
+            guard.0.take();
+            output
+        }
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        if let Some(counters) = self.0.take() {
+            counters.cancelled.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Statistics about the tasks the executor ran.
+///
+/// The counters start at zero when the plugin is loaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutorStats {
+    /// The number of times a task was put into the run queue, a task is queued
+    /// when it's spawned and every time it's woken up.
+    pub queued: u64,
+    /// The number of times a task was run.
+    pub run: u64,
+    /// The number of tasks that were dropped before they completed, either
+    /// because their `Task` handle was dropped, because the buffer they belong
+    /// to was closed or because they panicked.
+    pub cancelled: u64,
+    /// The number of tasks that are currently waiting in the run queue.
+    pub pending: usize,
+}
+
+/// The part of the executor that is needed to queue up a task.
+#[derive(Clone)]
+struct Scheduler {
+    sender: Weak<Mutex<Sender<()>>>,
+    queue: Weak<Mutex<VecDeque<ExecutorJob>>>,
+    notified: Arc<AtomicBool>,
+    counters: Arc<Counters>,
+}
+
+impl Scheduler {
+    fn schedule(&self, job: ExecutorJob) {
+        let sender = self.sender.upgrade();
+        let queue = self.queue.upgrade();
+
+        if let Some(q) = queue {
+            let sender = sender.expect("Futures queue exists but the channel got dropped");
+
+            q.lock()
+                .expect("Lock of the future queue of the Weechat executor is poisoned")
+                .push_back(job);
+            self.counters.queued.fetch_add(1, Ordering::Relaxed);
+
+            // Weechat needs to be woken up only once, the executor runs all
+            // the queued tasks once it's woken up.
+            if !self.notified.swap(true, Ordering::SeqCst) {
+                sender
+                    .lock()
+                    .expect("Weechat notification sender lock is poisoned")
+                    .send(())
+                    .expect("Can't notify Weechat to run a future");
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct WeechatExecutor {
     _hook: Arc<Mutex<Option<FdHook<Receiver<()>>>>>,
     sender: Arc<Mutex<Sender<()>>>,
     futures: FutureQueue,
     non_local_futures: Arc<Mutex<VecDeque<BoxFuture<'static, ()>>>>,
+    notified: Arc<AtomicBool>,
+    time_budget: Arc<AtomicU64>,
+    counters: Arc<Counters>,
 }
 
 impl FdHookCallback for WeechatExecutor {
@@ -57,33 +155,44 @@ impl FdHookCallback for WeechatExecutor {
             return;
         }
 
-        let future = self.futures.lock().unwrap().pop_front();
+        let start = Instant::now();
+        let time_budget = Duration::from_micros(self.time_budget.load(Ordering::Relaxed));
 
-        // Run a local future if there is one.
-        if let Some(task) = future {
-            match task {
-                ExecutorJob::Job(t) => {
-                    let _ = panic::catch_unwind(|| t.run());
-                }
-                ExecutorJob::BufferJob(t) => {
-                    let weechat = unsafe { Weechat::weechat() };
-                    let buffer_name = t.tag();
+        // Spawn the futures that were sent from other threads, this puts them
+        // into the local queue.
+        let non_local: Vec<_> = self.non_local_futures.lock().unwrap().drain(..).collect();
 
-                    let buffer = weechat.buffer_search("==", buffer_name);
+        for future in non_local {
+            self.spawn_local(future).detach();
+        }
 
-                    if buffer.is_some() {
-                        let _ = panic::catch_unwind(|| t.run());
-                    } else {
-                        t.cancel()
-                    }
-                }
+        // Queueing the futures above doesn't need to wake us up again, we
+        // are about to run them. Futures that are sent from other threads from
+        // now on are caught by the check for left over work below.
+        self.notified.store(false, Ordering::SeqCst);
+
+        // Run local futures until the queue is empty or the time budget is
+        // spent, at least one future is run per wakeup.
+        loop {
+            let job = self.futures.lock().unwrap().pop_front();
+
+            match job {
+                Some(job) => self.run_job(job),
+                None => break,
+            }
+
+            if start.elapsed() >= time_budget {
+                break;
             }
         }
 
-        let future = self.non_local_futures.lock().unwrap().pop_front();
-        // Spawn a future if there was one sent from another thread.
-        if let Some(future) = future {
-            self.spawn_local(future).detach();
+        // Wake up again on the next iteration of the Weechat main loop if
+        // there is work left.
+        let work_left = !self.futures.lock().unwrap().is_empty()
+            || !self.non_local_futures.lock().unwrap().is_empty();
+
+        if work_left {
+            let _ = self.notify();
         }
     }
 }
@@ -101,6 +210,9 @@ impl WeechatExecutor {
             sender,
             futures: queue,
             non_local_futures: non_local,
+            notified: Arc::new(AtomicBool::new(false)),
+            time_budget: Arc::new(AtomicU64::new(DEFAULT_TIME_BUDGET.as_micros() as u64)),
+            counters: Arc::new(Counters::default()),
         };
 
         let hook = FdHook::new(receiver, FdHookMode::Read, executor.clone())
@@ -111,30 +223,53 @@ impl WeechatExecutor {
         executor
     }
 
-    pub fn spawn_local<F>(&self, future: F) -> Task<F::Output>
-    where
-        F: Future + 'static,
-        F::Output: 'static,
-    {
-        let sender = Arc::downgrade(&self.sender);
-        let queue = Arc::downgrade(&self.futures);
+    fn scheduler(&self) -> Scheduler {
+        Scheduler {
+            sender: Arc::downgrade(&self.sender),
+            queue: Arc::downgrade(&self.futures),
+            notified: self.notified.clone(),
+            counters: self.counters.clone(),
+        }
+    }
 
-        let schedule = move |runnable| {
-            let sender = sender.upgrade();
-            let queue = queue.upgrade();
+    fn notify(&self) -> Result<(), ()> {
+        if self.notified.swap(true, Ordering::SeqCst) {
+            Ok(())
+        } else {
+            self.sender.lock().map_err(|_| ())?.send(()).map_err(|_| ())
+        }
+    }
 
-            if let Some(q) = queue {
-                let sender = sender.expect("Futures queue exists but the channel got dropped");
-                let mut weechat_notify =
-                    sender.lock().expect("Weechat notification sender lock is poisoned");
+    fn run_job(&self, job: ExecutorJob) {
+        match job {
+            ExecutorJob::Job(t) => {
+                self.counters.run.fetch_add(1, Ordering::Relaxed);
+                let _ = panic::catch_unwind(|| t.run());
+            }
+            ExecutorJob::BufferJob(t) => {
+                let weechat = unsafe { Weechat::weechat() };
+                let buffer_name = t.tag();
 
-                let mut queue =
-                    q.lock().expect("Lock of the future queue of the Weechat executor is poisoned");
+                let buffer = weechat.buffer_search("==", buffer_name);
 
-                queue.push_back(ExecutorJob::Job(runnable));
-                weechat_notify.send(()).expect("Can't notify Weechat to run a future");
+                if buffer.is_some() {
+                    self.counters.run.fetch_add(1, Ordering::Relaxed);
+                    let _ = panic::catch_unwind(|| t.run());
+                } else {
+                    t.cancel()
+                }
             }
-        };
+        }
+    }
+
+    pub fn spawn_local<F>(&self, future: F) -> Task<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let scheduler = self.scheduler();
+        let schedule = move |runnable| scheduler.schedule(ExecutorJob::Job(runnable));
+        let future = CancelGuard::wrap(self.counters.clone(), future);
 
         let (runnable, task) = async_task::spawn_local(future, schedule);
 
@@ -158,6 +293,30 @@ impl WeechatExecutor {
         }
     }
 
+    /// Get statistics about the tasks the executor ran.
+    pub fn stats() -> Option<ExecutorStats> {
+        #[allow(static_mut_refs)]
+        let executor = unsafe { _EXECUTOR.as_ref()? };
+        let counters = &executor.counters;
+
+        Some(ExecutorStats {
+            queued: counters.queued.load(Ordering::Relaxed),
+            run: counters.run.load(Ordering::Relaxed),
+            cancelled: counters.cancelled.load(Ordering::Relaxed),
+            pending: executor.futures.lock().unwrap().len(),
+        })
+    }
+
+    /// Set the time the executor may spend running tasks per wakeup.
+    pub fn set_time_budget(time_budget: Duration) {
+        #[allow(static_mut_refs)]
+        let executor = unsafe { _EXECUTOR.as_ref() };
+
+        if let Some(executor) = executor {
+            executor.time_budget.store(time_budget.as_micros() as u64, Ordering::Relaxed);
+        }
+    }
+
     /// Spawn a local Weechat future from the non-main thread.
     pub fn spawn_from_non_main<F>(future: F)
     where
@@ -167,14 +326,21 @@ impl WeechatExecutor {
         let executor = unsafe { _EXECUTOR.as_ref().expect("Executor wasn't started") };
 
         let future = Box::pin(future);
-        let mut queue = executor.non_local_futures.lock().unwrap();
-        queue.push_back(future);
-        executor
-            .sender
-            .lock()
-            .unwrap()
-            .send(())
-            .expect("Can't notify Weechat to spawn a non-local future");
+        executor.non_local_futures.lock().unwrap().push_back(future);
+        executor.notify().expect("Can't notify Weechat to spawn a non-local future");
+    }
+
+    /// Spawn a local Weechat future from the non-main thread, dropping the
+    /// future if the executor isn't running.
+    pub(crate) fn try_spawn_from_non_main<F>(future: F) -> Result<(), ()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        #[allow(static_mut_refs)]
+        let executor = unsafe { _EXECUTOR.as_ref().ok_or(())? };
+
+        executor.non_local_futures.lock().map_err(|_| ())?.push_back(Box::pin(future));
+        executor.notify()
     }
 
     /// Spawn a future that will run on the Weechat main loop.
@@ -196,25 +362,11 @@ impl WeechatExecutor {
         #[allow(static_mut_refs)]
         let executor = unsafe { _EXECUTOR.as_ref().expect("Executor wasn't started") };
 
-        let sender = Arc::downgrade(&executor.sender);
-        let queue = Arc::downgrade(&executor.futures);
-
+        let scheduler = executor.scheduler();
         let schedule = move |runnable| {
-            let sender = sender.upgrade();
-            let queue = queue.upgrade();
-
-            if let Some(q) = queue {
-                let sender = sender.expect("Futures queue exists but the channel got dropped");
-                let mut weechat_notify =
-                    sender.lock().expect("Weechat notification sender lock is poisoned");
-
-                let mut queue =
-                    q.lock().expect("Lock of the future queue of the Weechat executor is poisoned");
-
-                queue.push_back(ExecutorJob::BufferJob(BufferJob(runnable, buffer_name.clone())));
-                weechat_notify.send(()).expect("Can't notify Weechat to run a future");
-            }
+            scheduler.schedule(ExecutorJob::BufferJob(BufferJob(runnable, buffer_name.clone())))
         };
+        let future = CancelGuard::wrap(executor.counters.clone(), future);
 
         let (runnable, task) = async_task::spawn_local(future, schedule);
 
@@ -223,3 +375,32 @@ impl WeechatExecutor {
         task
     }
 }
+
+/// A future that lets the executor run once before resolving.
+///
+/// Awaiting the future puts the current task at the back of the queue, so the
+/// other ready tasks run before it continues. The task might continue within
+/// the same wakeup of the executor if the time budget isn't used up yet,
+/// Weechat gets to handle other events only once the budget is used up or the
+/// queue is empty.
+pub(crate) struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+pub(crate) fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, ffi::c_void, os::raw::c_char};
+use std::{
+    collections::HashMap,
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+};
 
 use weechat_sys::{t_hashtable, WEECHAT_HASHTABLE_STRING};
 
@@ -27,4 +31,40 @@ impl Weechat {
 
         hashtable
     }
+
+    pub(crate) unsafe fn hashtable_to_map(
+        &self,
+        hashtable: *mut t_hashtable,
+    ) -> HashMap<String, String> {
+        unsafe extern "C" fn map_cb(
+            data: *mut c_void,
+            _hashtable: *mut t_hashtable,
+            key: *const c_char,
+            value: *const c_char,
+        ) {
+            let map: &mut HashMap<String, String> = &mut *(data as *mut HashMap<String, String>);
+
+            if key.is_null() {
+                return;
+            }
+
+            let key = CStr::from_ptr(key).to_string_lossy().into_owned();
+            let value = if value.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(value).to_string_lossy().into_owned()
+            };
+
+            map.insert(key, value);
+        }
+
+        let mut map = HashMap::new();
+
+        if !hashtable.is_null() {
+            let hashtable_map_string = self.get().hashtable_map_string.unwrap();
+            hashtable_map_string(hashtable, Some(map_cb), &mut map as *mut _ as *mut c_void);
+        }
+
+        map
+    }
 }
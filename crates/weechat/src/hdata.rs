@@ -18,6 +18,23 @@ impl Weechat {
         hdata_get(self.ptr, name.as_ptr())
     }
 
+    pub(crate) unsafe fn hdata_get_list(&self, hdata: *mut t_hdata, name: &str) -> *mut c_void {
+        let hdata_get_list = self.get().hdata_get_list.unwrap();
+        let name = LossyCString::new(name);
+
+        hdata_get_list(hdata, name.as_ptr())
+    }
+
+    pub(crate) unsafe fn hdata_check_pointer(
+        &self,
+        hdata: *mut t_hdata,
+        list: *mut c_void,
+        pointer: *mut c_void,
+    ) -> bool {
+        let hdata_check_pointer = self.get().hdata_check_pointer.unwrap();
+        hdata_check_pointer(hdata, list, pointer) != 0
+    }
+
     pub(crate) unsafe fn hdata_pointer(
         &self,
         hdata: *mut t_hdata,
@@ -42,6 +59,18 @@ impl Weechat {
         hdata_integer(hdata, pointer, name.as_ptr())
     }
 
+    pub(crate) unsafe fn hdata_long(
+        &self,
+        hdata: *mut t_hdata,
+        pointer: *mut c_void,
+        name: &str,
+    ) -> i64 {
+        let hdata_long = self.get().hdata_long.unwrap();
+        let name = LossyCString::new(name);
+
+        hdata_long(hdata, pointer, name.as_ptr()) as _
+    }
+
     pub(crate) unsafe fn hdata_time(
         &self,
         hdata: *mut t_hdata,
@@ -1,11 +1,29 @@
-//! Bar items are used to display status information in Weechat.
+//! Bars and the bar items they contain are used to display status
+//! information in Weechat.
 use core::ptr;
-use std::os::raw::c_void;
+#[cfg(feature = "async")]
+use std::{cell::RefCell, rc::Rc, time::Duration};
+use std::{collections::HashMap, marker::PhantomData, os::raw::c_void};
 
+#[cfg(feature = "async")]
+use async_task::Task;
+#[cfg(feature = "async")]
+use futures::future::LocalBoxFuture;
 use libc::c_char;
-use weechat_sys::{t_gui_bar_item, t_gui_buffer, t_gui_window, t_hashtable, t_weechat_plugin};
+use weechat_sys::{
+    t_gui_bar, t_gui_bar_item, t_gui_buffer, t_gui_window, t_hashtable, t_weechat_plugin,
+};
 
-use crate::{buffer::Buffer, LossyCString, Weechat};
+#[cfg(feature = "async")]
+use crate::{
+    buffer::BufferProperty,
+    hooks::{RemainingCalls, SignalData, SignalHook, TimerHook},
+    ReturnCode,
+};
+use crate::{
+    buffer::{Buffer, Window},
+    LossyCString, Weechat,
+};
 
 /// Trait for the bar item callback
 ///
@@ -31,8 +49,71 @@ impl<T: FnMut(&Weechat, &Buffer) -> String + 'static> BarItemCallback for T {
     }
 }
 
+/// Trait for the bar item callback that receives the window the bar item is
+/// displayed in.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait BarItemWindowCallback: 'static {
+    /// The callback that should be called after the bar items
+    /// is marked to be updated.
+    ///
+    /// Should return a string that will be displayed by the bar item.
+    ///
+    /// # Arguments
+    ///
+    /// * `weeechat` - A reference to the weechat context.
+    ///
+    /// * `buffer` - The buffer the bar item is displayed for.
+    ///
+    /// * `window` - The window the bar item is displayed in, `None` if the
+    ///   bar item is displayed in a root bar.
+    ///
+    /// * `extra_info` - Extra information Weechat passes to the callback, for
+    ///   example when the bar item is evaluated in a `${...}` expression.
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        buffer: &Buffer,
+        window: Option<&Window>,
+        extra_info: &HashMap<String, String>,
+    ) -> String;
+}
+
+impl<
+        T: FnMut(&Weechat, &Buffer, Option<&Window>, &HashMap<String, String>) -> String + 'static,
+    > BarItemWindowCallback for T
+{
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        buffer: &Buffer,
+        window: Option<&Window>,
+        extra_info: &HashMap<String, String>,
+    ) -> String {
+        self(weechat, buffer, window, extra_info)
+    }
+}
+
+struct BufferOnlyCallback<T>(T);
+
+impl<T: BarItemCallback> BarItemWindowCallback for BufferOnlyCallback<T> {
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        buffer: &Buffer,
+        _window: Option<&Window>,
+        _extra_info: &HashMap<String, String>,
+    ) -> String {
+        self.0.callback(weechat, buffer)
+    }
+}
+
 struct BarItemCbData {
-    callback: Box<dyn BarItemCallback>,
+    callback: Box<dyn BarItemWindowCallback>,
+    // Callbacks that only take the buffer don't look at the extra info, so the
+    // hashtable isn't converted for them.
+    wants_extra_info: bool,
     weechat_ptr: *mut t_weechat_plugin,
 }
 
@@ -77,28 +158,80 @@ impl BarItem {
     ///     "rust/sample".to_owned()
     /// });
     /// ```
-    // TODO: Provide window object, the callback should accept a Window object
-    // wrapping a t_gui_window
-    //
     // TODO: If we're going to allow bar items to be searched for like we do for
     // buffers, we need to do something about the multiple ownership that may
     // come from this.
     pub fn new(name: &str, callback: impl BarItemCallback) -> Result<BarItem, ()> {
+        BarItem::new_helper(name, Box::new(BufferOnlyCallback(callback)), false)
+    }
+
+    /// Create a new bar item whose callback receives the window the bar item
+    /// is displayed in.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new bar item.
+    ///
+    /// * `callback` - The callback that should be called after the bar items is
+    ///   marked to be updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use weechat::Weechat;
+    /// # use weechat::buffer::{Buffer, Window};
+    /// # use weechat::hooks::BarItem;
+    /// let item = BarItem::new_with_window(
+    ///     "scroll_indicator",
+    ///     |_: &Weechat, _: &Buffer, window: Option<&Window>, _: &HashMap<String, String>| {
+    ///         match window {
+    ///             Some(window) if window.lines_after() > 0 => "-MORE-".to_owned(),
+    ///             _ => String::new(),
+    ///         }
+    ///     },
+    /// );
+    /// ```
+    pub fn new_with_window(
+        name: &str,
+        callback: impl BarItemWindowCallback,
+    ) -> Result<BarItem, ()> {
+        BarItem::new_helper(name, Box::new(callback), true)
+    }
+
+    fn new_helper(
+        name: &str,
+        callback: Box<dyn BarItemWindowCallback>,
+        wants_extra_info: bool,
+    ) -> Result<BarItem, ()> {
         unsafe extern "C" fn c_item_cb(
             pointer: *const c_void,
             _data: *mut c_void,
             _bar_item: *mut t_gui_bar_item,
-            _window: *mut t_gui_window,
+            window: *mut t_gui_window,
             buffer: *mut t_gui_buffer,
-            _extra_info: *mut t_hashtable,
+            extra_info: *mut t_hashtable,
         ) -> *mut c_char {
             let data: &mut BarItemCbData = { &mut *(pointer as *mut BarItemCbData) };
             let weechat = Weechat::from_ptr(data.weechat_ptr);
             let buffer = weechat.buffer_from_ptr(buffer);
+            let window = if window.is_null() {
+                None
+            } else {
+                Some(Window { weechat: weechat.ptr, ptr: window, phantom: PhantomData })
+            };
+            let extra_info = if data.wants_extra_info {
+                weechat.hashtable_to_map(extra_info)
+            } else {
+                HashMap::new()
+            };
 
             let cb_trait = &mut data.callback;
 
-            let ret = cb_trait.callback(&weechat, &buffer);
+            let ret = cb_trait.callback(&weechat, &buffer, window.as_ref(), &extra_info);
 
             // Weechat wants a malloc'ed string
             libc::strdup(LossyCString::new(ret).as_ptr())
@@ -106,8 +239,7 @@ impl BarItem {
         Weechat::check_thread();
         let weechat = unsafe { Weechat::weechat() };
 
-        let data =
-            Box::new(BarItemCbData { callback: Box::new(callback), weechat_ptr: weechat.ptr });
+        let data = Box::new(BarItemCbData { callback, wants_extra_info, weechat_ptr: weechat.ptr });
 
         let data_ref = Box::leak(data);
         let bar_item_new = weechat.get().bar_item_new.unwrap();
@@ -143,3 +275,806 @@ impl BarItem {
         Weechat::bar_item_update(&self.name);
     }
 }
+
+/// Trait for the async bar item callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+#[cfg(feature = "async")]
+pub trait BarItemCallbackAsync: 'static {
+    /// The callback that should be called if the content of the bar item
+    /// needs to be computed.
+    ///
+    /// Should return a future that resolves to the string that will be
+    /// displayed by the bar item, the buffer can't be borrowed by the future
+    /// so any data the future needs has to be taken out of the buffer first.
+    ///
+    /// # Arguments
+    ///
+    /// * `weeechat` - A reference to the weechat context.
+    ///
+    /// * `buffer` - The buffer the bar item is displayed for.
+    fn callback(&mut self, weechat: &Weechat, buffer: &Buffer) -> LocalBoxFuture<'static, String>;
+}
+
+#[cfg(feature = "async")]
+impl<T: FnMut(&Weechat, &Buffer) -> LocalBoxFuture<'static, String> + 'static> BarItemCallbackAsync
+    for T
+{
+    fn callback(&mut self, weechat: &Weechat, buffer: &Buffer) -> LocalBoxFuture<'static, String> {
+        self(weechat, buffer)
+    }
+}
+
+#[cfg(feature = "async")]
+struct CachedContent {
+    content: String,
+    generation: u64,
+}
+
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct AsyncBarItemState {
+    cache: HashMap<String, CachedContent>,
+    tasks: HashMap<String, Task<()>>,
+    refresh_timer: Option<TimerHook>,
+    // Incremented every time the cached content is discarded, cached content
+    // of an older generation is displayed but computed again.
+    generation: u64,
+}
+
+#[cfg(feature = "async")]
+struct AsyncBarItemCallback {
+    name: String,
+    throttle: Duration,
+    callback: Box<dyn BarItemCallbackAsync>,
+    state: Rc<RefCell<AsyncBarItemState>>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncBarItemState {
+    /// Forget the content and cancel the computation of the content for the
+    /// buffer with the given full name.
+    fn evict(state: &Rc<RefCell<AsyncBarItemState>>, key: &str) {
+        let task = {
+            let mut state = state.borrow_mut();
+            state.cache.remove(key);
+            state.tasks.remove(key)
+        };
+
+        // The task is cancelled only after the state isn't borrowed anymore,
+        // dropping the future might need to access the state.
+        drop(task);
+    }
+
+    /// Hook a buffer signal that evicts the cached content of the buffer
+    /// whose full name is returned by `key`.
+    fn evict_hook(
+        signal: &str,
+        state: &Rc<RefCell<AsyncBarItemState>>,
+        key: fn(&Buffer) -> String,
+    ) -> Result<SignalHook, ()> {
+        let weak_state = Rc::downgrade(state);
+
+        SignalHook::new(signal, move |_: &Weechat, _: &str, data: Option<SignalData>| {
+            if let (Some(SignalData::Buffer(buffer)), Some(state)) = (data, weak_state.upgrade()) {
+                AsyncBarItemState::evict(&state, &key(&buffer));
+            }
+
+            ReturnCode::Ok
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncBarItemCallback {
+    fn schedule_refresh(name: String, throttle: Duration, state: &Rc<RefCell<AsyncBarItemState>>) {
+        if state.borrow().refresh_timer.is_some() {
+            return;
+        }
+
+        let weak_state = Rc::downgrade(state);
+
+        let timer = TimerHook::new(throttle, 0, 0, move |_: &Weechat, _: RemainingCalls| {
+            let timer = weak_state.upgrade().and_then(|s| s.borrow_mut().refresh_timer.take());
+
+            Weechat::bar_item_update(&name);
+
+            // The timer can't be removed while its callback is running, drop
+            // it once the executor gets to run again.
+            if let Some(timer) = timer {
+                Weechat::spawn(async move { drop(timer) }).detach();
+            }
+        });
+
+        state.borrow_mut().refresh_timer = timer.ok();
+    }
+
+    fn spawn(&self, key: String, generation: u64, future: LocalBoxFuture<'static, String>) {
+        let weak_state = Rc::downgrade(&self.state);
+        let name = self.name.clone();
+        let throttle = self.throttle;
+        let task_key = key.clone();
+
+        let task = Weechat::spawn(async move {
+            let content = future.await;
+
+            if let Some(state) = weak_state.upgrade() {
+                let task = {
+                    let mut state = state.borrow_mut();
+                    state.cache.insert(task_key.clone(), CachedContent { content, generation });
+                    state.tasks.remove(&task_key)
+                };
+
+                // This is our own task, it's detached so that removing it from
+                // the state doesn't cancel it.
+                if let Some(task) = task {
+                    task.detach();
+                }
+
+                AsyncBarItemCallback::schedule_refresh(name, throttle, &state);
+            }
+        });
+
+        let previous = self.state.borrow_mut().tasks.insert(key, task);
+        drop(previous);
+    }
+}
+
+#[cfg(feature = "async")]
+impl BarItemWindowCallback for AsyncBarItemCallback {
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        buffer: &Buffer,
+        _window: Option<&Window>,
+        _extra_info: &HashMap<String, String>,
+    ) -> String {
+        let key = buffer.full_name().into_owned();
+
+        let (content, generation, needs_update) = {
+            let state = self.state.borrow();
+            let cached = state.cache.get(&key);
+
+            let valid = cached.is_some_and(|c| c.generation == state.generation);
+            let running = state.tasks.contains_key(&key);
+
+            (
+                cached.map(|c| c.content.clone()).unwrap_or_default(),
+                state.generation,
+                !valid && !running,
+            )
+        };
+
+        // The state isn't borrowed while the user callback runs, the
+        // callback might update the bar item itself. A computation that
+        // finishes after the content was discarded stores content of an older
+        // generation, the refresh that follows it starts a new one.
+        if needs_update {
+            let future = self.callback.callback(weechat, buffer);
+            self.spawn(key, generation, future);
+        }
+
+        content
+    }
+}
+
+/// A handle to a bar item whose content is computed asynchronously. The bar
+/// item is automatically removed when the object is dropped.
+///
+/// The content of the bar item is cached per buffer, once the future returned
+/// by the callback resolves the bar item is refreshed. Refreshes are throttled,
+/// futures that resolve while a refresh is pending are shown by that refresh.
+/// The cached content of a buffer is discarded once the buffer is closed or
+/// renamed.
+#[cfg(feature = "async")]
+pub struct BarItemAsync {
+    item: BarItem,
+    state: Rc<RefCell<AsyncBarItemState>>,
+    _closing_hook: SignalHook,
+    _renamed_hook: SignalHook,
+}
+
+#[cfg(feature = "async")]
+impl BarItemAsync {
+    /// The default time that is waited after a future resolves before the
+    /// bar item is refreshed.
+    pub const DEFAULT_THROTTLE: Duration = Duration::from_millis(100);
+
+    /// Create a new async bar item that can be added by a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new bar item.
+    ///
+    /// * `callback` - The callback that should be called if the content of
+    ///   the bar item needs to be computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use futures::future::{FutureExt, LocalBoxFuture};
+    /// # use weechat::Weechat;
+    /// # use weechat::buffer::Buffer;
+    /// # use weechat::hooks::BarItemAsync;
+    /// fn topic(_: &Weechat, buffer: &Buffer) -> LocalBoxFuture<'static, String> {
+    ///     let room = buffer.full_name().into_owned();
+    ///
+    ///     async move {
+    ///         // Fetch the topic of the room from the server.
+    ///         format!("Topic of {}", room)
+    ///     }
+    ///     .boxed_local()
+    /// }
+    ///
+    /// let item = BarItemAsync::new("room_topic", topic);
+    /// ```
+    pub fn new(name: &str, callback: impl BarItemCallbackAsync) -> Result<BarItemAsync, ()> {
+        BarItemAsync::new_with_throttle(name, BarItemAsync::DEFAULT_THROTTLE, callback)
+    }
+
+    /// Create a new async bar item with a custom refresh delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new bar item.
+    ///
+    /// * `throttle` - The time that is waited after a future resolves before
+    ///   the bar item is refreshed, futures that resolve in the meantime don't
+    ///   trigger another refresh but their content is shown by the pending
+    ///   one.
+    ///
+    /// * `callback` - The callback that should be called if the content of
+    ///   the bar item needs to be computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn new_with_throttle(
+        name: &str,
+        throttle: Duration,
+        callback: impl BarItemCallbackAsync,
+    ) -> Result<BarItemAsync, ()> {
+        let state = Rc::new(RefCell::new(AsyncBarItemState::default()));
+
+        let callback = AsyncBarItemCallback {
+            name: name.to_owned(),
+            throttle,
+            callback: Box::new(callback),
+            state: state.clone(),
+        };
+
+        let item = BarItem::new_helper(name, Box::new(callback), false)?;
+
+        let closing_hook = AsyncBarItemState::evict_hook("buffer_closing", &state, |buffer| {
+            buffer.full_name().into_owned()
+        })?;
+        let renamed_hook = AsyncBarItemState::evict_hook("buffer_renamed", &state, |buffer| {
+            buffer
+                .get_property(BufferProperty::OldFullName)
+                .and_then(|name| name.as_str().map(str::to_owned))
+                .unwrap_or_default()
+        })?;
+
+        Ok(BarItemAsync { item, state, _closing_hook: closing_hook, _renamed_hook: renamed_hook })
+    }
+
+    /// Discard the cached content and compute the content of the bar item
+    /// again.
+    ///
+    /// The previous content stays visible until the new content is ready.
+    /// Content that is being computed while this is called is computed again
+    /// once it's ready.
+    pub fn update(&self) {
+        self.state.borrow_mut().generation += 1;
+
+        self.item.update();
+    }
+}
+
+fn bool_str(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// The position of a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarPosition {
+    /// The bar is displayed at the top of the window or the screen.
+    Top,
+    /// The bar is displayed at the bottom of the window or the screen.
+    Bottom,
+    /// The bar is displayed on the left side of the window or the screen.
+    Left,
+    /// The bar is displayed on the right side of the window or the screen.
+    Right,
+}
+
+impl BarPosition {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BarPosition::Top => "top",
+            BarPosition::Bottom => "bottom",
+            BarPosition::Left => "left",
+            BarPosition::Right => "right",
+        }
+    }
+}
+
+/// The type of a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarType {
+    /// The bar is displayed once, outside of the windows.
+    Root,
+    /// The bar is displayed in every window.
+    Window,
+}
+
+impl BarType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BarType::Root => "root",
+            BarType::Window => "window",
+        }
+    }
+}
+
+/// The way the items of a bar are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarFilling {
+    /// The items are filled horizontally, with a space after each item.
+    Horizontal,
+    /// The items are filled vertically, with a new line after each item.
+    Vertical,
+    /// The items are displayed in columns, filled horizontally.
+    ColumnsHorizontal,
+    /// The items are displayed in columns, filled vertically.
+    ColumnsVertical,
+}
+
+impl BarFilling {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BarFilling::Horizontal => "horizontal",
+            BarFilling::Vertical => "vertical",
+            BarFilling::ColumnsHorizontal => "columns_horizontal",
+            BarFilling::ColumnsVertical => "columns_vertical",
+        }
+    }
+}
+
+/// A property of a bar that can be modified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BarProperty {
+    /// Should the bar be hidden.
+    Hidden(bool),
+    /// The priority of the bar, bars with a higher priority are displayed
+    /// closer to the edge of the screen.
+    Priority(i32),
+    /// The conditions that need to be true for the bar to be displayed.
+    Conditions(String),
+    /// The position of the bar.
+    Position(BarPosition),
+    /// The filling of the bar if it's positioned at the top or bottom.
+    FillingTopBottom(BarFilling),
+    /// The filling of the bar if it's positioned on the left or right.
+    FillingLeftRight(BarFilling),
+    /// The size of the bar, 0 means that the size is computed automatically.
+    Size(u32),
+    /// The maximum size of the bar, 0 means that there's no limit.
+    SizeMax(u32),
+    /// The color of the text in the bar.
+    ColorForeground(String),
+    /// The color of the delimiters in the bar.
+    ColorDelimiter(String),
+    /// The background color of the bar.
+    ColorBackground(String),
+    /// The background color of window bars that aren't in the active window.
+    ColorBackgroundInactive(String),
+    /// Should a separator line be displayed between the bar and the other
+    /// windows or bars.
+    Separator(bool),
+    /// The items of the bar.
+    Items(Vec<String>),
+}
+
+impl BarProperty {
+    fn name(&self) -> &'static str {
+        match self {
+            BarProperty::Hidden(_) => "hidden",
+            BarProperty::Priority(_) => "priority",
+            BarProperty::Conditions(_) => "conditions",
+            BarProperty::Position(_) => "position",
+            BarProperty::FillingTopBottom(_) => "filling_top_bottom",
+            BarProperty::FillingLeftRight(_) => "filling_left_right",
+            BarProperty::Size(_) => "size",
+            BarProperty::SizeMax(_) => "size_max",
+            BarProperty::ColorForeground(_) => "color_fg",
+            BarProperty::ColorDelimiter(_) => "color_delim",
+            BarProperty::ColorBackground(_) => "color_bg",
+            BarProperty::ColorBackgroundInactive(_) => "color_bg_inactive",
+            BarProperty::Separator(_) => "separator",
+            BarProperty::Items(_) => "items",
+        }
+    }
+
+    fn value(&self) -> String {
+        match self {
+            BarProperty::Hidden(value) | BarProperty::Separator(value) => {
+                bool_str(*value).to_owned()
+            }
+            BarProperty::Priority(value) => value.to_string(),
+            BarProperty::Position(position) => position.as_str().to_owned(),
+            BarProperty::FillingTopBottom(filling) | BarProperty::FillingLeftRight(filling) => {
+                filling.as_str().to_owned()
+            }
+            BarProperty::Size(value) | BarProperty::SizeMax(value) => value.to_string(),
+            BarProperty::Conditions(value)
+            | BarProperty::ColorForeground(value)
+            | BarProperty::ColorDelimiter(value)
+            | BarProperty::ColorBackground(value)
+            | BarProperty::ColorBackgroundInactive(value) => value.clone(),
+            BarProperty::Items(items) => items.join(","),
+        }
+    }
+}
+
+/// Builder for the creation of a bar.
+#[derive(Debug, Clone)]
+pub struct BarBuilder {
+    name: String,
+    hidden: bool,
+    priority: i32,
+    bar_type: BarType,
+    conditions: String,
+    position: BarPosition,
+    filling_top_bottom: BarFilling,
+    filling_left_right: BarFilling,
+    size: u32,
+    size_max: u32,
+    color_fg: String,
+    color_delim: String,
+    color_bg: String,
+    color_bg_inactive: String,
+    separator: bool,
+    items: Vec<String>,
+    remove_on_drop: bool,
+}
+
+impl BarBuilder {
+    /// Create a builder for a new bar.
+    ///
+    /// By default the bar is a window bar at the bottom of the window that
+    /// sizes itself automatically.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new bar.
+    pub fn new(name: &str) -> Self {
+        BarBuilder {
+            name: name.to_owned(),
+            hidden: false,
+            priority: 0,
+            bar_type: BarType::Window,
+            conditions: String::new(),
+            position: BarPosition::Bottom,
+            filling_top_bottom: BarFilling::Horizontal,
+            filling_left_right: BarFilling::Vertical,
+            size: 0,
+            size_max: 0,
+            color_fg: "default".to_owned(),
+            color_delim: "default".to_owned(),
+            color_bg: "default".to_owned(),
+            color_bg_inactive: "default".to_owned(),
+            separator: false,
+            items: Vec::new(),
+            remove_on_drop: false,
+        }
+    }
+
+    /// Should the bar be hidden.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Set the priority of the bar, bars with a higher priority are displayed
+    /// closer to the edge of the screen.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the type of the bar.
+    pub fn bar_type(mut self, bar_type: BarType) -> Self {
+        self.bar_type = bar_type;
+        self
+    }
+
+    /// Set the conditions that need to be true for the bar to be displayed.
+    ///
+    /// The conditions are evaluated, for example `${nicklist}` displays the
+    /// bar only in buffers that have a nicklist.
+    pub fn conditions(mut self, conditions: &str) -> Self {
+        self.conditions = conditions.to_owned();
+        self
+    }
+
+    /// Set the position of the bar.
+    pub fn position(mut self, position: BarPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the filling of the bar if it's positioned at the top or bottom.
+    pub fn filling_top_bottom(mut self, filling: BarFilling) -> Self {
+        self.filling_top_bottom = filling;
+        self
+    }
+
+    /// Set the filling of the bar if it's positioned on the left or right.
+    pub fn filling_left_right(mut self, filling: BarFilling) -> Self {
+        self.filling_left_right = filling;
+        self
+    }
+
+    /// Set the size of the bar, 0 means that the size is computed
+    /// automatically.
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the maximum size of the bar, 0 means that there's no limit.
+    pub fn size_max(mut self, size_max: u32) -> Self {
+        self.size_max = size_max;
+        self
+    }
+
+    /// Set the color of the text in the bar.
+    pub fn color_fg(mut self, color: &str) -> Self {
+        self.color_fg = color.to_owned();
+        self
+    }
+
+    /// Set the color of the delimiters in the bar.
+    pub fn color_delim(mut self, color: &str) -> Self {
+        self.color_delim = color.to_owned();
+        self
+    }
+
+    /// Set the background color of the bar.
+    pub fn color_bg(mut self, color: &str) -> Self {
+        self.color_bg = color.to_owned();
+        self
+    }
+
+    /// Set the background color of window bars that aren't in the active
+    /// window.
+    pub fn color_bg_inactive(mut self, color: &str) -> Self {
+        self.color_bg_inactive = color.to_owned();
+        self
+    }
+
+    /// Should a separator line be displayed between the bar and the other
+    /// windows or bars.
+    pub fn separator(mut self, separator: bool) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Add an item to the bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The name of the bar item, items joined with a `+` are
+    ///   displayed without a space between them.
+    pub fn item(mut self, item: &str) -> Self {
+        self.items.push(item.to_owned());
+        self
+    }
+
+    /// Should the bar be removed when the returned `Bar` is dropped.
+    ///
+    /// By default the bar is kept and saved in the Weechat configuration.
+    pub fn remove_on_drop(mut self, remove: bool) -> Self {
+        self.remove_on_drop = remove;
+        self
+    }
+
+    /// Build the configured bar.
+    ///
+    /// Returns an error if the bar couldn't be created, for example if a bar
+    /// with the same name already exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use weechat::hooks::{BarBuilder, BarPosition};
+    /// let bar = BarBuilder::new("rooms")
+    ///     .position(BarPosition::Left)
+    ///     .size(20)
+    ///     .item("buffer_plugin")
+    ///     .remove_on_drop(true)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn build(self) -> Result<Bar, ()> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let bar_new = weechat.get().bar_new.unwrap();
+
+        let name = LossyCString::new(&self.name);
+        let hidden = LossyCString::new(bool_str(self.hidden));
+        let priority = LossyCString::new(self.priority.to_string());
+        let bar_type = LossyCString::new(self.bar_type.as_str());
+        let conditions = LossyCString::new(&self.conditions);
+        let position = LossyCString::new(self.position.as_str());
+        let filling_top_bottom = LossyCString::new(self.filling_top_bottom.as_str());
+        let filling_left_right = LossyCString::new(self.filling_left_right.as_str());
+        let size = LossyCString::new(self.size.to_string());
+        let size_max = LossyCString::new(self.size_max.to_string());
+        let color_fg = LossyCString::new(&self.color_fg);
+        let color_delim = LossyCString::new(&self.color_delim);
+        let color_bg = LossyCString::new(&self.color_bg);
+        let color_bg_inactive = LossyCString::new(&self.color_bg_inactive);
+        let separator = LossyCString::new(bool_str(self.separator));
+        let items = LossyCString::new(self.items.join(","));
+
+        let bar_ptr = unsafe {
+            bar_new(
+                name.as_ptr(),
+                hidden.as_ptr(),
+                priority.as_ptr(),
+                bar_type.as_ptr(),
+                conditions.as_ptr(),
+                position.as_ptr(),
+                filling_top_bottom.as_ptr(),
+                filling_left_right.as_ptr(),
+                size.as_ptr(),
+                size_max.as_ptr(),
+                color_fg.as_ptr(),
+                color_delim.as_ptr(),
+                color_bg.as_ptr(),
+                color_bg_inactive.as_ptr(),
+                separator.as_ptr(),
+                items.as_ptr(),
+            )
+        };
+
+        if bar_ptr.is_null() {
+            Err(())
+        } else {
+            Ok(Bar { name: self.name, weechat: weechat.ptr, remove_on_drop: self.remove_on_drop })
+        }
+    }
+}
+
+/// A handle to a Weechat bar.
+///
+/// The bar is looked up by its name every time it's used, so the handle stays
+/// valid even if the bar is removed by the user, in which case modifications
+/// of the bar fail.
+pub struct Bar {
+    name: String,
+    weechat: *mut t_weechat_plugin,
+    remove_on_drop: bool,
+}
+
+impl Drop for Bar {
+    fn drop(&mut self) {
+        if self.remove_on_drop {
+            self.remove_helper();
+        }
+    }
+}
+
+impl Bar {
+    /// Search for an existing bar.
+    ///
+    /// Returns `None` if no bar with the given name exists. The bar is not
+    /// removed when the returned handle is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the bar.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn search(name: &str) -> Option<Bar> {
+        Weechat::check_thread();
+        let weechat = unsafe { Weechat::weechat() };
+
+        let bar = Bar { name: name.to_owned(), weechat: weechat.ptr, remove_on_drop: false };
+
+        if bar.ptr().is_null() {
+            None
+        } else {
+            Some(bar)
+        }
+    }
+
+    fn ptr(&self) -> *mut t_gui_bar {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let bar_search = weechat.get().bar_search.unwrap();
+        let name = LossyCString::new(&self.name);
+
+        unsafe { bar_search(name.as_ptr()) }
+    }
+
+    /// The name of the bar.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Modify a property of the bar.
+    ///
+    /// Returns an error if the bar doesn't exist anymore or if Weechat
+    /// rejected the value.
+    ///
+    /// # Arguments
+    ///
+    /// * `property` - The property that should be set, including its new
+    ///   value.
+    pub fn set(&self, property: BarProperty) -> Result<(), ()> {
+        let ptr = self.ptr();
+
+        if ptr.is_null() {
+            return Err(());
+        }
+
+        let weechat = Weechat::from_ptr(self.weechat);
+        let bar_set = weechat.get().bar_set.unwrap();
+
+        let name = LossyCString::new(property.name());
+        let value = LossyCString::new(property.value());
+
+        if unsafe { bar_set(ptr, name.as_ptr(), value.as_ptr()) } == 1 {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Set if the bar should be removed when this handle is dropped.
+    pub fn set_remove_on_drop(&mut self, remove: bool) {
+        self.remove_on_drop = remove;
+    }
+
+    /// Refresh the content of the bar on screen.
+    pub fn update(&self) {
+        let weechat = Weechat::from_ptr(self.weechat);
+        let bar_update = weechat.get().bar_update.unwrap();
+        let name = LossyCString::new(&self.name);
+
+        unsafe { bar_update(name.as_ptr()) };
+    }
+
+    fn remove_helper(&self) {
+        let ptr = self.ptr();
+
+        if !ptr.is_null() {
+            let weechat = Weechat::from_ptr(self.weechat);
+            let bar_remove = weechat.get().bar_remove.unwrap();
+
+            unsafe { bar_remove(ptr) };
+        }
+    }
+
+    /// Remove the bar.
+    pub fn remove(mut self) {
+        self.remove_helper();
+        self.remove_on_drop = false;
+    }
+}
@@ -3,7 +3,7 @@ use std::{borrow::Cow, ffi::CStr, os::raw::c_void, ptr};
 use libc::{c_char, c_int};
 use weechat_sys::{t_gui_buffer, t_weechat_plugin, WEECHAT_RC_OK};
 
-use super::Hook;
+use super::{name_with_priority, Hook};
 use crate::{buffer::Buffer, Args, LossyCString, ReturnCode, Weechat};
 
 /// Hook for a weechat command, the command is removed when the object is
@@ -13,6 +13,9 @@ pub struct Command {
     _hook_data: Box<CommandHookData>,
 }
 
+#[cfg(feature = "async")]
+hook_task_binding!(Command);
+
 /// Trait for the command callback
 ///
 /// A blanket implementation for pure `FnMut` functions exists, if data needs to
@@ -53,6 +56,8 @@ pub struct CommandSettings {
     argument_description: String,
     /// Completion template for the command.
     completion: Vec<String>,
+    /// Priority of the command hook.
+    priority: Option<i32>,
 }
 
 impl CommandSettings {
@@ -113,6 +118,19 @@ impl CommandSettings {
         self.completion.push(completion.into());
         self
     }
+
+    /// Set the priority of the command.
+    ///
+    /// If multiple plugins create the same command, the one with the highest
+    /// priority will be used. The default priority Weechat uses is 1000.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority the command hook should get.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
 }
 
 struct CommandHookData {
@@ -126,6 +144,9 @@ pub struct CommandRun {
     _hook_data: Box<CommandRunHookData>,
 }
 
+#[cfg(feature = "async")]
+hook_task_binding!(CommandRun);
+
 /// Trait for the command-run callback
 ///
 /// A blanket implementation for pure `FnMut` functions exists, if data needs to
@@ -185,6 +206,51 @@ impl CommandRun {
     /// .expect("Can't override buffer command");
     /// ```
     pub fn new(command: &str, callback: impl CommandRunCallback + 'static) -> Result<Self, ()> {
+        CommandRun::hook(command, callback)
+    }
+
+    /// Override an existing Weechat command with the given priority.
+    ///
+    /// Hooks with a higher priority are called first, the default priority
+    /// Weechat uses is 1000.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command to override (wildcard `*` is allowed), the
+    ///   command must not contain a priority prefix.
+    ///
+    /// * `priority` - The priority of the hook.
+    ///
+    /// * `callback` - The function that will be called when the command is run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::borrow::Cow;
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::hooks::CommandRun;
+    /// # use weechat::buffer::Buffer;
+    ///
+    /// let buffer_command = CommandRun::new_with_priority(
+    ///     "/buffer *",
+    ///     2000,
+    ///     |_: &Weechat, _: &Buffer, _: Cow<str>| ReturnCode::OkEat,
+    /// )
+    /// .expect("Can't override buffer command");
+    /// ```
+    pub fn new_with_priority(
+        command: &str,
+        priority: i32,
+        callback: impl CommandRunCallback + 'static,
+    ) -> Result<Self, ()> {
+        let command = name_with_priority(command, priority)?;
+        CommandRun::hook(&command, callback)
+    }
+
+    fn hook(command: &str, callback: impl CommandRunCallback + 'static) -> Result<Self, ()> {
         unsafe extern "C" fn c_hook_cb(
             pointer: *const c_void,
             _data: *mut c_void,
@@ -226,7 +292,7 @@ impl CommandRun {
         if hook_ptr.is_null() {
             Err(())
         } else {
-            let hook = Hook { ptr: hook_ptr, weechat_ptr: weechat.ptr };
+            let hook = Hook::new(hook_ptr, weechat.ptr);
 
             Ok(CommandRun { _hook: hook, _hook_data: hook_data })
         }
@@ -304,7 +370,12 @@ impl Command {
         Weechat::check_thread();
         let weechat = unsafe { Weechat::weechat() };
 
-        let name = LossyCString::new(command_settings.name);
+        let name = match command_settings.priority {
+            Some(priority) => name_with_priority(&command_settings.name, priority)?,
+            None => command_settings.name,
+        };
+
+        let name = LossyCString::new(name);
         let description = LossyCString::new(command_settings.description);
         let args = LossyCString::new(command_settings.arguments.join("||"));
         let args_description = LossyCString::new(command_settings.argument_description);
@@ -331,7 +402,7 @@ impl Command {
         };
         let hook_data = unsafe { Box::from_raw(data_ref) };
 
-        let hook = Hook { ptr: hook_ptr, weechat_ptr: weechat.ptr };
+        let hook = Hook::new(hook_ptr, weechat.ptr);
 
         if hook_ptr.is_null() {
             Err(())
@@ -5,7 +5,11 @@ use weechat_sys::{
     t_gui_buffer, t_gui_completion, t_weechat_plugin, WEECHAT_RC_ERROR, WEECHAT_RC_OK,
 };
 
-use crate::{buffer::Buffer, hooks::Hook, LossyCString, Weechat};
+use crate::{
+    buffer::Buffer,
+    hooks::{name_with_priority, Hook},
+    LossyCString, Weechat,
+};
 
 /// A handle to a completion item.
 pub struct Completion {
@@ -150,6 +154,9 @@ pub struct CompletionHook {
     _hook_data: Box<CompletionHookData>,
 }
 
+#[cfg(feature = "async")]
+hook_task_binding!(CompletionHook);
+
 struct CompletionHookData {
     #[allow(clippy::type_complexity)]
     callback: Box<dyn CompletionCallback>,
@@ -202,6 +209,40 @@ impl CompletionHook {
         completion_item: &str,
         description: &str,
         callback: impl CompletionCallback + 'static,
+    ) -> Result<CompletionHook, ()> {
+        CompletionHook::hook(completion_item, description, callback)
+    }
+
+    /// Create a new completion with the given priority.
+    ///
+    /// Hooks with a higher priority are called first, the default priority
+    /// Weechat uses is 1000.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the new completion, the name must not contain a
+    ///   priority prefix.
+    ///
+    /// * `priority` - The priority of the hook.
+    ///
+    /// * `description` - The description of the new completion.
+    ///
+    /// * `callback` - A function that will be called when the completion is
+    ///   used, the callback must populate the candidates for the completion.
+    pub fn new_with_priority(
+        completion_item: &str,
+        priority: i32,
+        description: &str,
+        callback: impl CompletionCallback + 'static,
+    ) -> Result<CompletionHook, ()> {
+        let completion_item = name_with_priority(completion_item, priority)?;
+        CompletionHook::hook(&completion_item, description, callback)
+    }
+
+    fn hook(
+        completion_item: &str,
+        description: &str,
+        callback: impl CompletionCallback + 'static,
     ) -> Result<CompletionHook, ()> {
         unsafe extern "C" fn c_hook_cb(
             pointer: *const c_void,
@@ -260,7 +301,7 @@ impl CompletionHook {
             return Err(());
         }
 
-        let hook = Hook { ptr: hook_ptr, weechat_ptr: weechat.ptr };
+        let hook = Hook::new(hook_ptr, weechat.ptr);
 
         Ok(CompletionHook { _hook: hook, _hook_data: hook_data })
     }
@@ -42,6 +42,9 @@ pub struct FdHook<F> {
     _hook_data: Box<FdHookData<F>>,
 }
 
+#[cfg(feature = "async")]
+hook_task_binding!(FdHook<F>);
+
 /// Callback trait for file descriptor based hooks.
 pub trait FdHookCallback {
     /// The concrete type of the hooked file descriptor object.
@@ -162,7 +165,7 @@ impl<F> FdHook<F> {
         };
 
         let hook_data = unsafe { Box::from_raw(data_ref) };
-        let hook = Hook { ptr: hook_ptr, weechat_ptr: weechat.ptr };
+        let hook = Hook::new(hook_ptr, weechat.ptr);
 
         if hook_ptr.is_null() {
             Err(())
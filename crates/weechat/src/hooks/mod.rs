@@ -3,6 +3,29 @@
 //! Weechat hooks are used for many different things, to create commands, to
 //! listen to events on a file descriptor, add completions to Weechat, etc.
 
+/// Implement the methods that bind tasks to the lifetime of a hook, the hook
+/// type needs a `_hook` field.
+#[cfg(feature = "async")]
+macro_rules! hook_task_binding {
+    ($hook:ident $(<$generic:ident>)?) => {
+        impl$(<$generic>)? $hook$(<$generic>)? {
+            /// Bind a task to the hook, the task is cancelled when the hook is
+            /// dropped.
+            ///
+            /// The output of the task is discarded.
+            pub fn bind_task<T: 'static>(&self, task: crate::Task<T>) {
+                self._hook.bind(Box::new(task));
+            }
+
+            /// Bind a task group to the hook, all the tasks of the group are
+            /// cancelled when the hook is dropped.
+            pub fn bind_task_group<T: 'static>(&self, group: crate::TaskGroup<T>) {
+                self._hook.bind(Box::new(group));
+            }
+        }
+    };
+}
+
 mod signal;
 
 mod bar;
@@ -13,7 +36,15 @@ mod fd;
 mod modifier;
 mod timer;
 
-pub use bar::{BarItem, BarItemCallback};
+#[cfg(feature = "async")]
+use std::cell::RefCell;
+
+pub use bar::{
+    Bar, BarBuilder, BarFilling, BarItem, BarItemCallback, BarItemWindowCallback, BarPosition,
+    BarProperty, BarType,
+};
+#[cfg(feature = "async")]
+pub use bar::{BarItemAsync, BarItemCallbackAsync};
 pub use commands::{Command, CommandCallback, CommandRun, CommandRunCallback, CommandSettings};
 pub use completion::{Completion, CompletionCallback, CompletionHook, CompletionPosition};
 pub use fd::{FdHook, FdHookCallback, FdHookMode};
@@ -23,6 +54,8 @@ pub use signal::{SignalCallback, SignalData, SignalHook};
 pub use timer::{RemainingCalls, TimerCallback, TimerHook};
 use weechat_sys::{t_hook, t_weechat_plugin};
 
+#[cfg(feature = "async")]
+use crate::task_group::BoundTask;
 use crate::Weechat;
 
 /// Weechat Hook type. The hook is unhooked automatically when the object is
@@ -30,6 +63,39 @@ use crate::Weechat;
 pub(crate) struct Hook {
     pub(crate) ptr: *mut t_hook,
     pub(crate) weechat_ptr: *mut t_weechat_plugin,
+    #[cfg(feature = "async")]
+    tasks: RefCell<Vec<Box<dyn BoundTask>>>,
+}
+
+impl Hook {
+    pub(crate) fn new(ptr: *mut t_hook, weechat_ptr: *mut t_weechat_plugin) -> Self {
+        Hook {
+            ptr,
+            weechat_ptr,
+            #[cfg(feature = "async")]
+            tasks: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Bind a task to the hook, the task is cancelled when the hook is
+    /// dropped.
+    #[cfg(feature = "async")]
+    pub(crate) fn bind(&self, task: Box<dyn BoundTask>) {
+        let finished = {
+            let mut tasks = self.tasks.borrow_mut();
+            let (finished, mut running): (Vec<_>, Vec<_>) =
+                std::mem::take(&mut *tasks).into_iter().partition(|t| t.is_finished());
+
+            running.push(task);
+            *tasks = running;
+
+            finished
+        };
+
+        // The finished tasks are dropped only after the tasks aren't borrowed
+        // anymore, dropping a future might bind other tasks.
+        drop(finished);
+    }
 }
 
 impl Drop for Hook {
@@ -37,5 +103,56 @@ impl Drop for Hook {
         let weechat = Weechat::from_ptr(self.weechat_ptr);
         let unhook = weechat.get().unhook.unwrap();
         unsafe { unhook(self.ptr) };
+
+        // Cancel the bound tasks only after the hook is gone, the tasks are
+        // taken out first since dropping a future might bind other tasks.
+        #[cfg(feature = "async")]
+        drop(self.tasks.take());
+    }
+}
+
+/// Prepend a WeeChat priority prefix, `NNN|`, to the given hook name.
+///
+/// Returns an error if the name is empty or if it already contains a priority
+/// prefix.
+pub(crate) fn name_with_priority(name: &str, priority: i32) -> Result<String, ()> {
+    if name.is_empty() || has_priority_prefix(name) {
+        Err(())
+    } else {
+        Ok(format!("{priority}|{name}"))
+    }
+}
+
+fn has_priority_prefix(name: &str) -> bool {
+    match name.split_once('|') {
+        Some((prefix, _)) => prefix.parse::<i32>().is_ok(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_is_prepended() {
+        assert_eq!(name_with_priority("buffer_switch", 2000), Ok("2000|buffer_switch".to_owned()));
+        assert_eq!(name_with_priority("test", -10), Ok("-10|test".to_owned()));
+    }
+
+    #[test]
+    fn empty_name_is_rejected() {
+        assert_eq!(name_with_priority("", 1000), Err(()));
+    }
+
+    #[test]
+    fn existing_priority_is_rejected() {
+        assert_eq!(name_with_priority("500|test", 1000), Err(()));
+        assert_eq!(name_with_priority("-5|test", 1000), Err(()));
+    }
+
+    #[test]
+    fn pipe_without_priority_is_allowed() {
+        assert_eq!(name_with_priority("a|b", 1000), Ok("1000|a|b".to_owned()));
     }
 }
@@ -3,7 +3,7 @@ use std::{borrow::Cow, ffi::CStr, os::raw::c_void, ptr};
 use libc::c_char;
 use weechat_sys::{t_gui_buffer, t_weechat_plugin};
 
-use super::Hook;
+use super::{name_with_priority, Hook};
 use crate::{buffer::Buffer, LossyCString, Weechat};
 
 /// Hook for a modifier, the hook is removed when the object is dropped.
@@ -13,6 +13,9 @@ pub struct ModifierHook {
     _hook_data: Box<ModifierHookData>,
 }
 
+#[cfg(feature = "async")]
+hook_task_binding!(ModifierHook);
+
 struct ModifierHookData {
     callback: Box<dyn ModifierCallback>,
     weechat_ptr: *mut t_weechat_plugin,
@@ -160,6 +163,39 @@ impl ModifierHook {
     /// ```
     #[cfg_attr(docsrs, doc(cfg(unsound)))]
     pub fn new(modifier_name: &str, callback: impl ModifierCallback + 'static) -> Result<Self, ()> {
+        ModifierHook::hook(modifier_name, callback)
+    }
+
+    /// Hook a modifier with the given priority.
+    ///
+    /// Hooks with a higher priority are called first, the default priority
+    /// Weechat uses is 1000.
+    ///
+    /// # Arguments
+    ///
+    /// * `modifier_name` - The modifier to hook, the name must not contain a
+    ///   priority prefix.
+    ///
+    /// * `priority` - The priority of the hook.
+    ///
+    /// * `callback` - A function or a struct that implements ModifierCallback,
+    ///   the callback method of the trait will be called when the modifier is
+    ///   fired.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    #[cfg_attr(docsrs, doc(cfg(unsound)))]
+    pub fn new_with_priority(
+        modifier_name: &str,
+        priority: i32,
+        callback: impl ModifierCallback + 'static,
+    ) -> Result<Self, ()> {
+        let modifier_name = name_with_priority(modifier_name, priority)?;
+        ModifierHook::hook(&modifier_name, callback)
+    }
+
+    fn hook(modifier_name: &str, callback: impl ModifierCallback + 'static) -> Result<Self, ()> {
         unsafe extern "C" fn c_hook_cb(
             pointer: *const c_void,
             _data: *mut c_void,
@@ -217,7 +253,7 @@ impl ModifierHook {
         };
 
         let hook_data = unsafe { Box::from_raw(data_ref) };
-        let hook = Hook { ptr: hook_ptr, weechat_ptr: weechat.ptr };
+        let hook = Hook::new(hook_ptr, weechat.ptr);
 
         if hook_ptr.is_null() {
             Err(())
@@ -9,7 +9,7 @@ use std::{
 
 use weechat_sys::{t_gui_buffer, t_weechat_plugin};
 
-use super::Hook;
+use super::{name_with_priority, Hook};
 use crate::{
     buffer::{Buffer, InnerBuffer, InnerBuffers},
     LossyCString, ReturnCode, Weechat,
@@ -21,6 +21,9 @@ pub struct SignalHook {
     _hook_data: Box<SignalHookData>,
 }
 
+#[cfg(feature = "async")]
+hook_task_binding!(SignalHook);
+
 struct SignalHookData {
     callback: Box<dyn SignalCallback>,
     weechat_ptr: *mut t_weechat_plugin,
@@ -226,6 +229,52 @@ impl SignalHook {
     /// );
     /// ```
     pub fn new(signal_name: &str, callback: impl SignalCallback + 'static) -> Result<Self, ()> {
+        SignalHook::hook(signal_name, callback)
+    }
+
+    /// Hook a signal with the given priority.
+    ///
+    /// Hooks with a higher priority are called first, the default priority
+    /// Weechat uses is 1000.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal_name` - The signal to hook (wildcard `*` is allowed), the name
+    ///   must not contain a priority prefix.
+    ///
+    /// * `priority` - The priority of the hook.
+    ///
+    /// * `callback` - A function or a struct that implements SignalCallback,
+    ///   the callback method of the trait will be called when the signal is
+    ///   fired.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::{Weechat, ReturnCode};
+    /// # use weechat::hooks::{SignalData, SignalHook};
+    /// let signal_hook = SignalHook::new_with_priority(
+    ///     "buffer_switch",
+    ///     2000,
+    ///     |_weechat: &Weechat, _signal_name: &str, _data: Option<SignalData>| {
+    ///         ReturnCode::Ok
+    ///     },
+    /// );
+    /// ```
+    pub fn new_with_priority(
+        signal_name: &str,
+        priority: i32,
+        callback: impl SignalCallback + 'static,
+    ) -> Result<Self, ()> {
+        let signal_name = name_with_priority(signal_name, priority)?;
+        SignalHook::hook(&signal_name, callback)
+    }
+
+    fn hook(signal_name: &str, callback: impl SignalCallback + 'static) -> Result<Self, ()> {
         unsafe extern "C" fn c_hook_cb(
             pointer: *const c_void,
             _data: *mut c_void,
@@ -268,7 +317,7 @@ impl SignalHook {
         };
 
         let hook_data = unsafe { Box::from_raw(data_ref) };
-        let hook = Hook { ptr: hook_ptr, weechat_ptr: weechat.ptr };
+        let hook = Hook::new(hook_ptr, weechat.ptr);
 
         if hook_ptr.is_null() {
             Err(())
@@ -12,6 +12,9 @@ pub struct TimerHook {
     _hook_data: Box<TimerHookData>,
 }
 
+#[cfg(feature = "async")]
+hook_task_binding!(TimerHook);
+
 /// Enum representing how many calls a timer still has.
 pub enum RemainingCalls {
     /// Infinitely many remaining calls.
@@ -133,10 +136,7 @@ impl TimerHook {
         if hook_ptr.is_null() {
             Err(())
         } else {
-            Ok(TimerHook {
-                _hook: Hook { ptr: hook_ptr, weechat_ptr: weechat.ptr },
-                _hook_data: hook_data,
-            })
+            Ok(TimerHook { _hook: Hook::new(hook_ptr, weechat.ptr), _hook_data: hook_data })
         }
     }
 }
@@ -239,6 +239,16 @@ pub enum InfolistVariable<'a> {
     Buffer(Buffer<'a>),
 }
 
+impl<'a> Infolist<'a> {
+    pub(crate) fn from_raw(
+        weechat: &'a Weechat,
+        ptr: *mut t_infolist,
+        infolist_name: &str,
+    ) -> Infolist<'a> {
+        Infolist { ptr, infolist_name: infolist_name.to_owned(), weechat }
+    }
+}
+
 impl Infolist<'_> {
     fn is_pointer_buffer(infolist_name: &str, variable_name: &str) -> bool {
         matches!(
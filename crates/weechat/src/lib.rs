@@ -45,6 +45,11 @@ use std::ffi::CString;
 mod executor;
 mod hashtable;
 mod hdata;
+mod regex;
+#[cfg(feature = "async")]
+mod remote;
+#[cfg(feature = "async")]
+mod task_group;
 mod weechat;
 
 #[cfg(feature = "config_macro")]
@@ -60,11 +65,13 @@ pub mod buffer;
 pub mod config;
 pub mod hooks;
 pub mod infolist;
+pub mod upgrade;
 
 pub use libc;
 pub use weechat_macro::plugin;
 pub use weechat_sys;
 
+use crate::upgrade::UpgradeWriter;
 pub use crate::weechat::{Args, Prefix, Weechat};
 
 /// Weechat plugin trait.
@@ -85,10 +92,37 @@ pub trait Plugin: Sized {
     ///
     /// * `args` - Arguments passed to the plugin when it is loaded.
     fn init(weechat: &Weechat, args: Args) -> Result<Self, ()>;
+
+    /// The upgrade method for the plugin.
+    ///
+    /// This will be called when the user runs the `/upgrade` command, the
+    /// plugin should write out the state it wishes to restore once the new
+    /// Weechat binary loads it again. The state can be restored in the init
+    /// method using [`Weechat::upgrade_read`] if the args contain the
+    /// `--upgrade` flag.
+    ///
+    /// Buffers survive the upgrade, but their callbacks don't, they need to be
+    /// attached again after the state is restored.
+    ///
+    /// The default implementation doesn't write anything out.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `writer` - The writer that should be used to save the plugin state.
+    fn upgrade(&self, weechat: &Weechat, writer: &UpgradeWriter) -> Result<(), ()> {
+        let _ = (weechat, writer);
+        Ok(())
+    }
 }
 
 #[cfg(feature = "async")]
-pub use executor::Task;
+pub use executor::{ExecutorStats, Task};
+#[cfg(feature = "async")]
+pub use remote::WeechatRemote;
+#[cfg(feature = "async")]
+pub use task_group::TaskGroup;
 
 /// Status values for Weechat callbacks
 pub enum ReturnCode {
@@ -0,0 +1,49 @@
+use std::mem::MaybeUninit;
+
+use libc::{regex_t, REG_EXTENDED, REG_NOSUB};
+
+use crate::{LossyCString, Weechat};
+
+/// A compiled POSIX extended regular expression.
+///
+/// The regular expression is compiled by Weechat, so flags like `(?i)` at the
+/// start of the expression are supported.
+pub(crate) struct Regex {
+    inner: Box<regex_t>,
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    ///
+    /// Returns an error if the regular expression isn't valid.
+    pub(crate) fn new(weechat: &Weechat, regex: &str) -> Result<Self, ()> {
+        let string_regcomp = weechat.get().string_regcomp.unwrap();
+        let regex = LossyCString::new(regex);
+
+        let mut inner: Box<MaybeUninit<regex_t>> = Box::new(MaybeUninit::uninit());
+
+        let ret = unsafe {
+            string_regcomp(inner.as_mut_ptr() as _, regex.as_ptr(), REG_EXTENDED | REG_NOSUB)
+        };
+
+        if ret == 0 {
+            let inner = unsafe { Box::from_raw(Box::into_raw(inner) as *mut regex_t) };
+            Ok(Regex { inner })
+        } else {
+            Err(())
+        }
+    }
+
+    /// Does the given text match the regular expression.
+    pub(crate) fn is_match(&self, text: &str) -> bool {
+        let text = LossyCString::new(text);
+
+        unsafe { libc::regexec(&*self.inner, text.as_ptr(), 0, std::ptr::null_mut(), 0) == 0 }
+    }
+}
+
+impl Drop for Regex {
+    fn drop(&mut self) {
+        unsafe { libc::regfree(&mut *self.inner) };
+    }
+}
@@ -0,0 +1,70 @@
+use futures::{channel::oneshot, future::Future};
+
+use crate::{executor::WeechatExecutor, Weechat};
+
+/// A handle that makes it possible to use the Weechat API from any thread.
+///
+/// The handle can be cloned and sent to other threads, calls made through it
+/// are run on the main Weechat thread.
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::Weechat;
+/// # use futures::executor::block_on;
+/// # let weechat = unsafe { Weechat::weechat() };
+/// let remote = weechat.remote();
+///
+/// std::thread::spawn(move || {
+///     let nick = block_on(remote.call(|_| Weechat::info_get("irc_nick", "libera")));
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeechatRemote {
+    _private: (),
+}
+
+impl WeechatRemote {
+    /// Run a closure on the main Weechat thread.
+    ///
+    /// The returned future resolves to the return value of the closure once
+    /// the closure ran. It resolves to an error if the closure panicked or if
+    /// the plugin was unloaded before the closure could run.
+    ///
+    /// The future must not be blocked on from the main Weechat thread, the
+    /// closure can only run once control is given back to Weechat.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure that should be run on the main Weechat thread.
+    pub fn call<F, T>(&self, f: F) -> impl Future<Output = Result<T, ()>>
+    where
+        F: FnOnce(&Weechat) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+
+        let queued = WeechatExecutor::try_spawn_from_non_main(async move {
+            let weechat = unsafe { Weechat::weechat() };
+            let _ = sender.send(f(weechat));
+        });
+
+        async move {
+            queued?;
+            receiver.await.map_err(|_| ())
+        }
+    }
+}
+
+impl Weechat {
+    /// Get a handle that makes it possible to use the Weechat API from other
+    /// threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn remote(&self) -> WeechatRemote {
+        Weechat::check_thread();
+        WeechatRemote { _private: () }
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::future::{self, poll_fn};
+use weechat_sys::t_gui_buffer;
+
+use crate::{buffer::BufferHandle, executor::Task, Weechat};
+
+/// A task whose result isn't needed, it only needs to be cancelled at the
+/// right time.
+pub(crate) trait BoundTask {
+    fn is_finished(&self) -> bool;
+}
+
+impl<T: 'static> BoundTask for Task<T> {
+    fn is_finished(&self) -> bool {
+        Task::is_finished(self)
+    }
+}
+
+impl<T: 'static> BoundTask for TaskGroup<T> {
+    fn is_finished(&self) -> bool {
+        self.tasks.iter().all(|t| t.is_finished())
+    }
+}
+
+type BufferTasks = HashMap<usize, Vec<Box<dyn BoundTask>>>;
+
+static mut BUFFER_TASKS: Option<BufferTasks> = None;
+
+fn bind_to_buffer(buffer: &BufferHandle, task: Box<dyn BoundTask>) {
+    let buffer_ptr = buffer.buffer_ptr();
+
+    // The task is dropped, and thus cancelled, right away if the buffer is
+    // already closed.
+    if buffer_ptr.is_null() {
+        return;
+    }
+
+    let finished = {
+        #[allow(static_mut_refs)]
+        let tasks = unsafe { BUFFER_TASKS.get_or_insert_with(HashMap::new) };
+        let buffer_tasks = tasks.entry(buffer_ptr as usize).or_default();
+
+        let (finished, mut running): (Vec<_>, Vec<_>) =
+            std::mem::take(buffer_tasks).into_iter().partition(|t| t.is_finished());
+
+        running.push(task);
+        *buffer_tasks = running;
+
+        finished
+    };
+
+    // The finished tasks are dropped only after the map isn't referenced
+    // anymore, dropping a future might bind or cancel other tasks.
+    drop(finished);
+}
+
+/// Cancel all the tasks that are bound to the given buffer.
+pub(crate) fn cancel_buffer_tasks(buffer_ptr: *mut t_gui_buffer) {
+    #[allow(static_mut_refs)]
+    let tasks = unsafe { BUFFER_TASKS.as_mut().and_then(|t| t.remove(&(buffer_ptr as usize))) };
+
+    // The tasks are dropped only after they were removed from the map,
+    // dropping a future might bind or cancel other tasks.
+    drop(tasks);
+}
+
+/// Cancel all the tasks that are bound to buffers.
+pub(crate) fn free() {
+    #[allow(static_mut_refs)]
+    let tasks = unsafe { BUFFER_TASKS.take() };
+
+    drop(tasks);
+}
+
+/// A set of tasks that are cancelled together.
+///
+/// All the tasks of the group are cancelled when the group is dropped. A group
+/// can be bound to a buffer using [`TaskGroup::bind_to_buffer`] or to a hook
+/// using the `bind_task_group` method of the hook, for example
+/// [`SignalHook::bind_task_group`].
+///
+/// [`SignalHook::bind_task_group`]: crate::hooks::SignalHook::bind_task_group
+///
+/// # Example
+///
+/// ```no_run
+/// # use weechat::TaskGroup;
+/// # async fn sync_room(room: &str) -> usize { 0 }
+/// let mut group = TaskGroup::new();
+///
+/// for room in ["#rust", "#weechat"] {
+///     group.spawn(async move { sync_room(room).await });
+/// }
+///
+/// # async {
+/// while let Some(events) = group.join_next().await {
+///     println!("Synced {} events", events);
+/// }
+/// # };
+/// ```
+pub struct TaskGroup<T> {
+    tasks: Vec<Task<T>>,
+}
+
+impl<T: 'static> Default for TaskGroup<T> {
+    fn default() -> Self {
+        TaskGroup::new()
+    }
+}
+
+impl<T: 'static> TaskGroup<T> {
+    /// Create a new empty task group.
+    pub fn new() -> Self {
+        TaskGroup { tasks: Vec::new() }
+    }
+
+    /// Spawn a new future on the main Weechat thread and add its task to the
+    /// group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread or if
+    /// the executor isn't running anymore.
+    pub fn spawn<F>(&mut self, future: F)
+    where
+        F: Future<Output = T> + 'static,
+    {
+        self.tasks.push(Weechat::spawn(future));
+    }
+
+    /// Add an already spawned task to the group.
+    pub fn add(&mut self, task: Task<T>) {
+        self.tasks.push(task);
+    }
+
+    /// The number of tasks in the group.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Does the group contain no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Cancel all the tasks of the group.
+    pub fn cancel_all(&mut self) {
+        self.tasks.clear();
+    }
+
+    /// Wait for the next task of the group to finish.
+    ///
+    /// The finished task is removed from the group. Returns `None` if the
+    /// group doesn't contain any tasks.
+    ///
+    /// Every time the returned future is woken up all the tasks of the group
+    /// are polled, and every task wakes the future up once it finishes. For
+    /// large groups whose outputs aren't needed in completion order
+    /// [`TaskGroup::join_all`] is cheaper.
+    ///
+    /// Dropping the returned future doesn't cancel any tasks.
+    pub async fn join_next(&mut self) -> Option<T> {
+        poll_fn(|cx: &mut Context<'_>| {
+            if self.tasks.is_empty() {
+                return Poll::Ready(None);
+            }
+
+            for i in 0..self.tasks.len() {
+                if let Poll::Ready(output) = Pin::new(&mut self.tasks[i]).poll(cx) {
+                    self.tasks.swap_remove(i);
+                    return Poll::Ready(Some(output));
+                }
+            }
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Wait for all the tasks of the group to finish.
+    ///
+    /// Returns the outputs of the tasks in the order the tasks were added to
+    /// the group.
+    pub async fn join_all(self) -> Vec<T> {
+        future::join_all(self.tasks).await
+    }
+
+    /// Bind the group to a buffer, all the tasks of the group are cancelled
+    /// when the buffer is closed.
+    ///
+    /// The tasks are cancelled right away if the buffer is already closed.
+    pub fn bind_to_buffer(self, buffer: &BufferHandle) {
+        bind_to_buffer(buffer, Box::new(self));
+    }
+}
+
+impl BufferHandle {
+    /// Bind a task to the buffer, the task is cancelled when the buffer is
+    /// closed.
+    ///
+    /// The output of the task is discarded. The task is cancelled right away
+    /// if the buffer is already closed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use weechat::{buffer::BufferBuilder, Weechat};
+    /// # async fn sync_loop() {}
+    /// # let buffer_handle = BufferBuilder::new("test").build().unwrap();
+    /// buffer_handle.bind_task(Weechat::spawn(sync_loop()));
+    /// ```
+    pub fn bind_task<T: 'static>(&self, task: Task<T>) {
+        bind_to_buffer(self, Box::new(task));
+    }
+}
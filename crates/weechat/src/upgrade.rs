@@ -0,0 +1,391 @@
+//! Save and restore the plugin state across a Weechat `/upgrade`.
+//!
+//! When the user runs `/upgrade`, Weechat saves its state, executes the new
+//! Weechat binary and restores the saved state. Plugins can take part in this
+//! by implementing the [`Plugin::upgrade`] method. Objects that are written
+//! there can be read back in the [`Plugin::init`] method using
+//! [`Weechat::upgrade_read`], the [`Args`] will contain the `--upgrade` flag in
+//! that case.
+//!
+//! [`Args`]: crate::Args
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use weechat::{Args, Plugin, Weechat};
+//! # use weechat::infolist::{Infolist, InfolistVariable};
+//! # use weechat::upgrade::{UpgradeItem, UpgradeWriter};
+//! struct Irc {
+//!     servers: Vec<String>,
+//! }
+//!
+//! const SERVER_OBJECT: i32 = 1;
+//!
+//! impl Plugin for Irc {
+//!     fn init(weechat: &Weechat, args: Args) -> Result<Self, ()> {
+//!         let mut servers = Vec::new();
+//!
+//!         if args.is_upgrade() {
+//!             weechat.upgrade_read(|_: &Weechat, object_id: i32, infolist: &mut Infolist| {
+//!                 for item in infolist {
+//!                     if let (SERVER_OBJECT, Some(InfolistVariable::String(name))) =
+//!                         (object_id, item.get("name"))
+//!                     {
+//!                         servers.push(name.to_string());
+//!                     }
+//!                 }
+//!                 Ok(())
+//!             })?;
+//!         }
+//!
+//!         Ok(Irc { servers })
+//!     }
+//!
+//!     fn upgrade(&self, _: &Weechat, writer: &UpgradeWriter) -> Result<(), ()> {
+//!         let items: Vec<UpgradeItem> =
+//!             self.servers.iter().map(|s| UpgradeItem::new().add_string("name", s)).collect();
+//!
+//!         writer.write_object(SERVER_OBJECT, &items)
+//!     }
+//! }
+//! ```
+
+use std::{
+    cell::Cell,
+    ffi::{c_void, CStr, CString},
+    mem::ManuallyDrop,
+    ptr,
+    time::SystemTime,
+};
+
+use libc::c_int;
+use weechat_sys::{t_infolist, t_upgrade_file, t_weechat_plugin, WEECHAT_RC_ERROR, WEECHAT_RC_OK};
+
+use crate::{
+    hooks::{SignalData, SignalHook},
+    infolist::Infolist,
+    LossyCString, Plugin, ReturnCode, Weechat,
+};
+
+#[derive(Debug, Clone)]
+enum UpgradeVariable {
+    Integer(i32),
+    String(String),
+    Time(SystemTime),
+}
+
+/// An item of an object that will be written to the upgrade file.
+///
+/// Once read back, every item will be a separate item of the [`Infolist`]
+/// that is passed to the upgrade read callback.
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeItem {
+    variables: Vec<(String, UpgradeVariable)>,
+}
+
+impl UpgradeItem {
+    /// Create a new empty upgrade item.
+    pub fn new() -> Self {
+        UpgradeItem::default()
+    }
+
+    /// Add an integer variable to the item.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// * `value` - The value of the variable.
+    pub fn add_integer(mut self, name: &str, value: i32) -> Self {
+        self.variables.push((name.to_owned(), UpgradeVariable::Integer(value)));
+        self
+    }
+
+    /// Add a string variable to the item.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// * `value` - The value of the variable.
+    pub fn add_string(mut self, name: &str, value: &str) -> Self {
+        self.variables.push((name.to_owned(), UpgradeVariable::String(value.to_owned())));
+        self
+    }
+
+    /// Add a time variable to the item.
+    ///
+    /// The time is stored with a precision of one second.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable.
+    ///
+    /// * `value` - The value of the variable.
+    pub fn add_time(mut self, name: &str, value: SystemTime) -> Self {
+        self.variables.push((name.to_owned(), UpgradeVariable::Time(value)));
+        self
+    }
+}
+
+/// A handle to the upgrade file of the plugin that objects can be written to.
+///
+/// The file is named after the plugin and is created once the first object is
+/// written, it is closed once the writer is dropped.
+pub struct UpgradeWriter {
+    weechat_ptr: *mut t_weechat_plugin,
+    ptr: Cell<*mut t_upgrade_file>,
+}
+
+impl Drop for UpgradeWriter {
+    fn drop(&mut self) {
+        let ptr = self.ptr.get();
+
+        if !ptr.is_null() {
+            let weechat = Weechat::from_ptr(self.weechat_ptr);
+            let upgrade_close = weechat.get().upgrade_close.unwrap();
+            unsafe { upgrade_close(ptr) };
+        }
+    }
+}
+
+impl UpgradeWriter {
+    pub(crate) fn new(weechat: &Weechat) -> UpgradeWriter {
+        UpgradeWriter { weechat_ptr: weechat.ptr, ptr: Cell::new(ptr::null_mut()) }
+    }
+
+    fn file(&self, weechat: &Weechat) -> Result<*mut t_upgrade_file, ()> {
+        if self.ptr.get().is_null() {
+            let upgrade_new = weechat.get().upgrade_new.unwrap();
+            let filename = weechat.upgrade_file_name();
+
+            let ptr = unsafe { upgrade_new(filename.as_ptr(), None, ptr::null(), ptr::null_mut()) };
+
+            if ptr.is_null() {
+                return Err(());
+            }
+
+            self.ptr.set(ptr);
+        }
+
+        Ok(self.ptr.get())
+    }
+
+    /// Write an object to the upgrade file.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_id` - An id of the object, this will be passed to the read
+    ///   callback and can be used to distinguish between different kinds of
+    ///   objects.
+    ///
+    /// * `items` - The items the object consists of.
+    ///
+    /// Returns an error if the upgrade file could not be created or the object
+    /// could not be written.
+    pub fn write_object(&self, object_id: i32, items: &[UpgradeItem]) -> Result<(), ()> {
+        let weechat = Weechat::from_ptr(self.weechat_ptr);
+        let file = self.file(&weechat)?;
+
+        let infolist = weechat.infolist_from_items(items)?;
+
+        let upgrade_write_object = weechat.get().upgrade_write_object.unwrap();
+        let ret = unsafe { upgrade_write_object(file, object_id, infolist) };
+
+        let infolist_free = weechat.get().infolist_free.unwrap();
+        unsafe { infolist_free(infolist) };
+
+        if ret == 1 {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Trait for the upgrade read callback.
+///
+/// A blanket implementation for pure `FnMut` functions exists, if data needs to
+/// be passed to the callback implement this over your struct.
+pub trait UpgradeReadCallback {
+    /// Callback that will be called for every object that is read from the
+    /// upgrade file.
+    ///
+    /// # Arguments
+    ///
+    /// * `weechat` - A Weechat context.
+    ///
+    /// * `object_id` - The id the object was written with.
+    ///
+    /// * `infolist` - The items of the object.
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        object_id: i32,
+        infolist: &mut Infolist,
+    ) -> Result<(), ()>;
+}
+
+impl<T: FnMut(&Weechat, i32, &mut Infolist) -> Result<(), ()>> UpgradeReadCallback for T {
+    fn callback(
+        &mut self,
+        weechat: &Weechat,
+        object_id: i32,
+        infolist: &mut Infolist,
+    ) -> Result<(), ()> {
+        self(weechat, object_id, infolist)
+    }
+}
+
+struct UpgradeReadData<'a> {
+    callback: &'a mut dyn UpgradeReadCallback,
+    weechat_ptr: *mut t_weechat_plugin,
+}
+
+impl Weechat {
+    fn upgrade_file_name(&self) -> CString {
+        let plugin_get_name = self.get().plugin_get_name.unwrap();
+
+        unsafe { CStr::from_ptr(plugin_get_name(self.ptr)).to_owned() }
+    }
+
+    fn infolist_from_items(&self, items: &[UpgradeItem]) -> Result<*mut t_infolist, ()> {
+        let infolist_new = self.get().infolist_new.unwrap();
+        let infolist_new_item = self.get().infolist_new_item.unwrap();
+        let new_var_integer = self.get().infolist_new_var_integer.unwrap();
+        let new_var_string = self.get().infolist_new_var_string.unwrap();
+        let new_var_time = self.get().infolist_new_var_time.unwrap();
+        let infolist_free = self.get().infolist_free.unwrap();
+
+        let infolist = unsafe { infolist_new(self.ptr) };
+
+        if infolist.is_null() {
+            return Err(());
+        }
+
+        for item in items {
+            let item_ptr = unsafe { infolist_new_item(infolist) };
+
+            if item_ptr.is_null() {
+                unsafe { infolist_free(infolist) };
+                return Err(());
+            }
+
+            for (name, variable) in &item.variables {
+                let name = LossyCString::new(name);
+
+                let var_ptr = match variable {
+                    UpgradeVariable::Integer(value) => unsafe {
+                        new_var_integer(item_ptr, name.as_ptr(), *value)
+                    },
+                    UpgradeVariable::String(value) => {
+                        let value = LossyCString::new(value);
+                        unsafe { new_var_string(item_ptr, name.as_ptr(), value.as_ptr()) }
+                    }
+                    UpgradeVariable::Time(value) => {
+                        let time = value
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .map(|d| d.as_secs())
+                            .unwrap_or_default();
+                        unsafe { new_var_time(item_ptr, name.as_ptr(), time as _) }
+                    }
+                };
+
+                if var_ptr.is_null() {
+                    unsafe { infolist_free(infolist) };
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(infolist)
+    }
+
+    /// Read the objects that were written to the upgrade file of the plugin.
+    ///
+    /// This should be called in the [`Plugin::init`] method if the [`Args`]
+    /// contain the `--upgrade` flag.
+    ///
+    /// [`Args`]: crate::Args
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback that will be called for every object that
+    ///   was written to the upgrade file.
+    ///
+    /// Returns an error if the upgrade file couldn't be opened or if the
+    /// callback returned an error for any of the objects.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    pub fn upgrade_read(&self, mut callback: impl UpgradeReadCallback) -> Result<(), ()> {
+        unsafe extern "C" fn c_read_cb(
+            pointer: *const c_void,
+            _data: *mut c_void,
+            _upgrade_file: *mut t_upgrade_file,
+            object_id: c_int,
+            infolist: *mut t_infolist,
+        ) -> c_int {
+            let data: &mut UpgradeReadData = { &mut *(pointer as *mut UpgradeReadData) };
+            let weechat = Weechat::from_ptr(data.weechat_ptr);
+
+            // The infolist is owned and freed by Weechat.
+            let mut infolist = ManuallyDrop::new(Infolist::from_raw(&weechat, infolist, "upgrade"));
+
+            match data.callback.callback(&weechat, object_id, &mut infolist) {
+                Ok(()) => WEECHAT_RC_OK,
+                Err(()) => WEECHAT_RC_ERROR,
+            }
+        }
+
+        Weechat::check_thread();
+
+        let mut data = UpgradeReadData { callback: &mut callback, weechat_ptr: self.ptr };
+
+        let upgrade_new = self.get().upgrade_new.unwrap();
+        let upgrade_read = self.get().upgrade_read.unwrap();
+        let upgrade_close = self.get().upgrade_close.unwrap();
+
+        let filename = self.upgrade_file_name();
+
+        unsafe {
+            let file = upgrade_new(
+                filename.as_ptr(),
+                Some(c_read_cb),
+                &mut data as *mut _ as *const c_void,
+                ptr::null_mut(),
+            );
+
+            if file.is_null() {
+                return Err(());
+            }
+
+            let ret = upgrade_read(file);
+            upgrade_close(file);
+
+            if ret == 1 {
+                Ok(())
+            } else {
+                Err(())
+            }
+        }
+    }
+}
+
+/// Hook the upgrade signal so the plugin state is saved on `/upgrade`.
+///
+/// This should never be called by the user, this is called internally but
+/// needs to be public because it's used in the macro expansion of the plugin
+/// init method.
+#[doc(hidden)]
+pub fn hook_upgrade<P: Plugin + 'static>(plugin: fn() -> &'static mut P) -> Result<SignalHook, ()> {
+    SignalHook::new("upgrade", move |weechat: &Weechat, _: &str, _: Option<SignalData>| {
+        let writer = UpgradeWriter::new(weechat);
+
+        match plugin().upgrade(weechat, &writer) {
+            Ok(()) => ReturnCode::Ok,
+            Err(()) => ReturnCode::Error,
+        }
+    })
+}
@@ -1,13 +1,13 @@
 //! Main weechat module
 
-#[cfg(feature = "async")]
-use std::future::Future;
 use std::{
     ffi::{CStr, CString},
     panic::PanicHookInfo,
     path::PathBuf,
     ptr, vec,
 };
+#[cfg(feature = "async")]
+use std::{future::Future, time::Duration};
 
 #[cfg(feature = "async")]
 pub use async_task::Task;
@@ -16,7 +16,7 @@ use libc::{c_char, c_int};
 use weechat_sys::t_weechat_plugin;
 
 #[cfg(feature = "async")]
-use crate::executor::WeechatExecutor;
+use crate::executor::{ExecutorStats, WeechatExecutor};
 use crate::LossyCString;
 
 /// An iterator over the arguments of a Weechat command, yielding a String value
@@ -74,6 +74,17 @@ impl Args {
             .collect();
         Args { iter: args.into_iter() }
     }
+
+    /// Was the plugin loaded as part of a Weechat `/upgrade`.
+    ///
+    /// If this returns true, the plugin state that was saved in the
+    /// [`Plugin::upgrade`] method can be restored using
+    /// [`Weechat::upgrade_read`].
+    ///
+    /// [`Plugin::upgrade`]: crate::Plugin::upgrade
+    pub fn is_upgrade(&self) -> bool {
+        self.iter.as_slice().iter().any(|arg| arg == "--upgrade")
+    }
 }
 
 impl std::fmt::Debug for Args {
@@ -215,6 +226,8 @@ impl Weechat {
     /// This should never be called by the user. This is called internally.
     #[doc(hidden)]
     pub unsafe fn free() {
+        #[cfg(feature = "async")]
+        crate::task_group::free();
         #[cfg(feature = "async")]
         WeechatExecutor::free();
     }
@@ -275,9 +288,9 @@ impl Weechat {
         Weechat::check_thread();
         let weechat = unsafe { Weechat::weechat() };
 
-        #[cfg(not(weechat410))]
+        #[cfg(weechat420)]
         let printf_datetime_tags = weechat.get().printf_datetime_tags.unwrap();
-        #[cfg(weechat410)]
+        #[cfg(not(weechat420))]
         let printf_datetime_tags = weechat.get().printf_date_tags.unwrap();
 
         let fmt = LossyCString::new("%s");
@@ -287,7 +300,7 @@ impl Weechat {
             printf_datetime_tags(
                 ptr::null_mut(),
                 0,
-                #[cfg(not(weechat410))]
+                #[cfg(weechat420)]
                 0,
                 ptr::null(),
                 fmt.as_ptr(),
@@ -700,6 +713,36 @@ impl Weechat {
         WeechatExecutor::spawn_from_non_main(future)
     }
 
+    /// Set the time the executor may spend running futures every time it's
+    /// woken up, defaults to 10 milliseconds.
+    ///
+    /// The executor runs futures until none of them are ready or until the
+    /// time budget is spent, the remaining futures are run on the next
+    /// iteration of the Weechat main loop. A smaller budget keeps Weechat
+    /// responsive, a bigger one lets futures finish faster.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    #[cfg(feature = "async")]
+    pub fn set_executor_time_budget(time_budget: Duration) {
+        Weechat::check_thread();
+        WeechatExecutor::set_time_budget(time_budget)
+    }
+
+    /// Get statistics about the futures the executor ran.
+    ///
+    /// Returns `None` if the executor isn't running.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method is not called from the main Weechat thread.
+    #[cfg(feature = "async")]
+    pub fn executor_stats() -> Option<ExecutorStats> {
+        Weechat::check_thread();
+        WeechatExecutor::stats()
+    }
+
     #[cfg(feature = "async")]
     pub(crate) fn spawn_buffer_cb<F>(buffer_name: String, future: F) -> Task<F::Output>
     where